@@ -6,6 +6,7 @@
  */
 
 import {
+  AccountMeta,
   Connection,
   PublicKey,
   Keypair,
@@ -13,6 +14,7 @@ import {
   Commitment,
 } from '@solana/web3.js';
 import { AnchorProvider, Program, Wallet, BN } from '@coral-xyz/anchor';
+import axios from 'axios';
 import * as fs from 'fs';
 import * as path from 'path';
 import {
@@ -34,19 +36,55 @@ import {
   InvalidRoyaltyError,
 } from './types';
 import {
+  BUBBLEGUM_PROGRAM_ID,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  SPL_NOOP_PROGRAM_ID,
   derivePlatformPDA,
   deriveEventPDA,
   deriveTicketPDA,
   deriveReentrancyGuardPDA,
+  deriveRoleRegistryPDA,
+  deriveEventTreePDA,
+  deriveTreeConfigPDA,
   toBase58,
   fromBase58,
 } from './pda';
+import { deriveOwnerCommitment } from './commitment';
+
+/**
+ * A ticket cNFT's leaf as the program's instructions take it, with the
+ * accounts needed to prove it
+ */
+interface TicketLeaf {
+  leafArgs: {
+    treeIndex: number;
+    tierIndex: number;
+    ticketNumber: number;
+    section: string;
+    row: number;
+    seat: number;
+    root: number[];
+    nonce: BN;
+    index: number;
+  };
+  metadata: {
+    name: string;
+    uri: string;
+    sellerFeeBasisPoints: number;
+    creators: { address: PublicKey; verified: boolean; share: number }[];
+  };
+  proofAccounts: AccountMeta[];
+  eventTree: PublicKey;
+  merkleTree: PublicKey;
+  leafOwner: PublicKey;
+}
 
 /**
  * Main blockchain client for TicketToken operations
  */
 export class BlockchainClient {
   private connection: Connection;
+  private dasRpcUrl: string;
   private program: Program;
   private platformWallet: Keypair;
   private programId: PublicKey;
@@ -60,6 +98,7 @@ export class BlockchainClient {
       this.validateConfig(config);
       this.commitment = (config.commitment || 'confirmed') as Commitment;
       this.connection = new Connection(config.rpcUrl, { commitment: this.commitment });
+      this.dasRpcUrl = config.dasRpcUrl || config.rpcUrl;
       this.programId = fromBase58(config.programId);
       this.platformWallet = this.loadWallet(config.platformWalletPath);
       this.program = this.initializeProgram();
@@ -235,22 +274,31 @@ export class BlockchainClient {
   async registerTicket(params: RegisterTicketParams): Promise<RegisterTicketResult> {
     try {
       const eventPubkey = fromBase58(params.eventPda);
-      const nftAssetId = fromBase58(params.nftAssetId);
+      const event = await (this.program.account as any).event.fetch(eventPubkey);
+      const leaf = await this.fetchTicketLeaf(eventPubkey, event.treeCount, fromBase58(params.nftAssetId));
 
-      const [ticketPda] = deriveTicketPDA(this.programId, eventPubkey, params.ticketId);
+      const [platformPda] = derivePlatformPDA(this.programId);
+      const [roleRegistry] = deriveRoleRegistryPDA(this.programId);
+      const [ticketPda] = deriveTicketPDA(this.programId, eventPubkey, leaf.leafArgs.ticketNumber);
+      const ownerCommitment = deriveOwnerCommitment(params.ownerId, params.ownerSalt);
 
       const signature = await (this.program.methods as any)
-        .registerTicket(
-          new BN(params.ticketId.toString()),
-          nftAssetId,
-          params.ownerId
-        )
+        .registerTicket(leaf.leafArgs, leaf.metadata, Array.from(ownerCommitment))
         .accounts({
           authority: this.platformWallet.publicKey,
+          platform: platformPda,
+          roleRegistry,
           event: eventPubkey,
+          venue: event.venue,
+          venueStaff: null,
+          eventTree: leaf.eventTree,
           ticket: ticketPda,
+          leafOwner: leaf.leafOwner,
+          merkleTree: leaf.merkleTree,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
+        .remainingAccounts(leaf.proofAccounts)
         .rpc();
 
       await this.connection.confirmTransaction(signature);
@@ -258,7 +306,7 @@ export class BlockchainClient {
       return {
         ticketPda: toBase58(ticketPda),
         signature,
-        ticketId: params.ticketId,
+        ticketId: leaf.leafArgs.ticketNumber,
       };
     } catch (error) {
       throw this.handleError(error, 'registerTicket');
@@ -272,16 +320,51 @@ export class BlockchainClient {
         throw new TicketAlreadyUsedError(params.ticketPda);
       }
 
+      // The holder signs the cNFT transfer, so only custodial tickets can move from here
+      const leafOwner = fromBase58(ticketInfo.ownerWallet);
+      if (!leafOwner.equals(this.platformWallet.publicKey)) {
+        throw new BlockchainError(
+          `Ticket ${params.ticketPda} is held by ${ticketInfo.ownerWallet}, not the platform wallet`,
+          'NOT_TICKET_HOLDER'
+        );
+      }
+
       const eventPubkey = fromBase58(params.eventPda);
       const ticketPubkey = fromBase58(params.ticketPda);
+      const event = await (this.program.account as any).event.fetch(eventPubkey);
+      const leaf = await this.fetchTicketLeaf(eventPubkey, event.treeCount, fromBase58(ticketInfo.nftAssetId));
+
+      const [platformPda] = derivePlatformPDA(this.programId);
+      const [roleRegistry] = deriveRoleRegistryPDA(this.programId);
+      const [treeConfig] = deriveTreeConfigPDA(leaf.merkleTree);
+      const newOwnerCommitment = deriveOwnerCommitment(params.newOwnerId, params.newOwnerSalt);
 
       const signature = await (this.program.methods as any)
-        .transferTicket(params.newOwnerId)
+        .transferTicket(
+          leaf.leafArgs,
+          leaf.metadata,
+          Array.from(newOwnerCommitment),
+          new BN((params.salePrice ?? 0).toString())
+        )
         .accounts({
           authority: this.platformWallet.publicKey,
+          platform: platformPda,
+          roleRegistry,
           event: eventPubkey,
+          venue: event.venue,
+          venueStaff: null,
           ticket: ticketPubkey,
+          leafOwner,
+          newLeafOwner: params.newLeafOwner ? fromBase58(params.newLeafOwner) : leafOwner,
+          eventTree: leaf.eventTree,
+          treeConfig,
+          merkleTree: leaf.merkleTree,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
+        .remainingAccounts(leaf.proofAccounts)
         .rpc();
 
       await this.connection.confirmTransaction(signature);
@@ -301,11 +384,17 @@ export class BlockchainClient {
 
       const eventPubkey = fromBase58(params.eventPda);
       const ticketPubkey = fromBase58(params.ticketPda);
+      const event = await (this.program.account as any).event.fetch(eventPubkey);
+      const [platformPda] = derivePlatformPDA(this.programId);
 
       const signature = await (this.program.methods as any)
-        .verifyTicket()
+        .verifyTicket(params.gate)
         .accounts({
           validator: this.platformWallet.publicKey,
+          platform: platformPda,
+          venue: event.venue,
+          venueStaff: null,
+          holder: null,
           event: eventPubkey,
           ticket: ticketPubkey,
         })
@@ -328,7 +417,8 @@ export class BlockchainClient {
         event: toBase58(ticket.event),
         ticketId: ticket.ticketId.toNumber(),
         nftAssetId: toBase58(ticket.nftAssetId),
-        currentOwnerId: ticket.currentOwnerId,
+        ownerCommitment: Buffer.from(ticket.ownerCommitment),
+        ownerWallet: toBase58(ticket.ownerWallet),
         used: ticket.used,
         verifiedAt: ticket.verifiedAt ? ticket.verifiedAt.toNumber() : null,
         transferCount: ticket.transferCount,
@@ -345,6 +435,82 @@ export class BlockchainClient {
     return this.getTicketStatus(ticketPda);
   }
 
+  /**
+   * Looks up a ticket cNFT's leaf and proof through the DAS API. The
+   * program rehashes the leaf from these, so they must match what was minted.
+   */
+  private async fetchTicketLeaf(
+    eventPubkey: PublicKey,
+    treeCount: number,
+    assetId: PublicKey
+  ): Promise<TicketLeaf> {
+    const [asset, assetProof] = await Promise.all([
+      this.dasRequest<any>('getAsset', { id: toBase58(assetId) }),
+      this.dasRequest<any>('getAssetProof', { id: toBase58(assetId) }),
+    ]);
+    if (!asset.compression?.compressed) {
+      throw new BlockchainError(`Asset ${toBase58(assetId)} is not a compressed NFT`);
+    }
+
+    const merkleTree = fromBase58(asset.compression.tree);
+    let treeIndex = -1;
+    for (let i = 0; i < treeCount && treeIndex < 0; i++) {
+      const [eventTreePda] = deriveEventTreePDA(this.programId, eventPubkey, i);
+      const eventTree = await (this.program.account as any).eventTree.fetch(eventTreePda);
+      if (eventTree.merkleTree.equals(merkleTree)) {
+        treeIndex = i;
+      }
+    }
+    if (treeIndex < 0) {
+      throw new BlockchainError(`Asset ${toBase58(assetId)} is not in one of the event's trees`);
+    }
+
+    // Ticket URIs end in /tickets/{number}?tier=&section=&row=&seat=
+    const uri = new URL(asset.content.json_uri);
+    const nonce = asset.compression.leaf_id;
+
+    return {
+      leafArgs: {
+        treeIndex,
+        tierIndex: Number(uri.searchParams.get('tier')),
+        ticketNumber: Number(uri.pathname.split('/').pop()),
+        section: uri.searchParams.get('section') ?? '',
+        row: Number(uri.searchParams.get('row')),
+        seat: Number(uri.searchParams.get('seat')),
+        root: Array.from(fromBase58(assetProof.root).toBytes()),
+        nonce: new BN(nonce),
+        index: nonce,
+      },
+      metadata: {
+        name: asset.content.metadata.name,
+        uri: asset.content.json_uri,
+        sellerFeeBasisPoints: asset.royalty.basis_points,
+        creators: asset.creators.map((creator: any) => ({
+          address: fromBase58(creator.address),
+          verified: creator.verified,
+          share: creator.share,
+        })),
+      },
+      // Full proof; the tree's canopy covers any nodes beyond what it needs
+      proofAccounts: assetProof.proof.map((node: string) => ({
+        pubkey: fromBase58(node),
+        isSigner: false,
+        isWritable: false,
+      })),
+      eventTree: deriveEventTreePDA(this.programId, eventPubkey, treeIndex)[0],
+      merkleTree,
+      leafOwner: fromBase58(asset.ownership.owner),
+    };
+  }
+
+  private async dasRequest<T>(method: string, params: Record<string, unknown>): Promise<T> {
+    const { data } = await axios.post(this.dasRpcUrl, { jsonrpc: '2.0', id: method, method, params });
+    if (data.error) {
+      throw new BlockchainError(`${method} failed: ${data.error.message}`);
+    }
+    return data.result as T;
+  }
+
   // ==========================================================================
   // UTILITY METHODS
   // ==========================================================================
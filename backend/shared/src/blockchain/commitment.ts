@@ -0,0 +1,54 @@
+/**
+ * Ticket Owner Commitment Helpers
+ * 
+ * Tickets store sha256(salt || ownerId) on-chain instead of the backend
+ * user ID. The salt never leaves the backend, so the ID can't be read
+ * back or brute-forced from account data, logs or events.
+ */
+
+import * as crypto from 'crypto';
+
+/** Salt length in bytes */
+export const OWNER_SALT_LENGTH = 32;
+
+/**
+ * Generates a random salt for a ticket owner
+ * 
+ * @returns 32 random bytes; store alongside the ticket in the database
+ */
+export function generateOwnerSalt(): Buffer {
+  return crypto.randomBytes(OWNER_SALT_LENGTH);
+}
+
+/**
+ * Derives the on-chain owner commitment
+ * Matches Ticket::owner_commitment in the program
+ * 
+ * @param ownerId - The owner's user ID from the database
+ * @param salt - The owner's 32-byte salt
+ * @returns 32-byte commitment to pass to registerTicket / transferTicket
+ */
+export function deriveOwnerCommitment(ownerId: string, salt: Uint8Array): Buffer {
+  if (salt.length !== OWNER_SALT_LENGTH) {
+    throw new Error(`Owner salt must be ${OWNER_SALT_LENGTH} bytes`);
+  }
+  return crypto.createHash('sha256').update(salt).update(ownerId, 'utf8').digest();
+}
+
+/**
+ * Checks whether an on-chain commitment belongs to a user
+ * 
+ * @param commitment - The ticket's ownerCommitment field
+ * @param ownerId - The candidate owner's user ID
+ * @param salt - The salt stored for the ticket
+ * @returns true if the commitment was derived from ownerId and salt
+ */
+export function matchesOwnerCommitment(
+  commitment: Uint8Array,
+  ownerId: string,
+  salt: Uint8Array
+): boolean {
+  const expected = deriveOwnerCommitment(ownerId, salt);
+  return commitment.length === expected.length &&
+    crypto.timingSafeEqual(Buffer.from(commitment), expected);
+}
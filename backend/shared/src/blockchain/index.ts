@@ -33,6 +33,14 @@ export {
   InvalidRoyaltyError,
 } from './types';
 
+// Owner commitment helpers
+export {
+  OWNER_SALT_LENGTH,
+  generateOwnerSalt,
+  deriveOwnerCommitment,
+  matchesOwnerCommitment,
+} from './commitment';
+
 // PDA derivation helpers
 export {
   derivePlatformPDA,
@@ -41,6 +49,9 @@ export {
   deriveTicketPDA,
   deriveReentrancyGuardPDA,
   deriveListingReentrancyGuardPDA,
+  deriveRoleRegistryPDA,
+  deriveEventTreePDA,
+  deriveTreeConfigPDA,
   toBase58,
   fromBase58,
 } from './pda';
@@ -7,6 +7,15 @@
 
 import { PublicKey } from '@solana/web3.js';
 
+/** Metaplex Bubblegum program, which mints and moves ticket cNFTs */
+export const BUBBLEGUM_PROGRAM_ID = new PublicKey('BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY');
+
+/** SPL account compression program, which owns the merkle trees */
+export const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey('cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK');
+
+/** SPL noop program Bubblegum logs leaves through */
+export const SPL_NOOP_PROGRAM_ID = new PublicKey('noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV');
+
 /**
  * Derives the platform PDA
  * Seeds: ["platform"]
@@ -87,6 +96,54 @@ export function deriveTicketPDA(
   );
 }
 
+/**
+ * Derives the admin role registry PDA
+ * Seeds: ["role_registry"]
+ * 
+ * @param programId - The TicketToken program ID
+ * @returns [PublicKey, bump] tuple
+ */
+export function deriveRoleRegistryPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('role_registry')],
+    programId
+  );
+}
+
+/**
+ * Derives an event tree PDA
+ * Seeds: ["event_tree", eventPubkey, treeIndex]
+ * 
+ * @param programId - The TicketToken program ID
+ * @param eventPubkey - The event's public key
+ * @param treeIndex - Position of the tree in the event's tree list (u8)
+ * @returns [PublicKey, bump] tuple
+ */
+export function deriveEventTreePDA(
+  programId: PublicKey,
+  eventPubkey: PublicKey,
+  treeIndex: number
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('event_tree'), eventPubkey.toBuffer(), Buffer.from([treeIndex])],
+    programId
+  );
+}
+
+/**
+ * Derives the Bubblegum tree config PDA for a merkle tree
+ * Seeds: [merkleTree] under the Bubblegum program
+ * 
+ * @param merkleTree - The merkle tree's public key
+ * @returns [PublicKey, bump] tuple
+ */
+export function deriveTreeConfigPDA(merkleTree: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [merkleTree.toBuffer()],
+    BUBBLEGUM_PROGRAM_ID
+  );
+}
+
 /**
  * Derives a reentrancy guard PDA for event operations
  * Seeds: ["reentrancy", context, identifier]
@@ -26,6 +26,9 @@ export interface BlockchainConfig {
   
   /** Transaction commitment level (default: 'confirmed') */
   commitment?: Commitment;
+  
+  /** DAS-enabled RPC endpoint for ticket cNFT lookups (default: rpcUrl) */
+  dasRpcUrl?: string;
 }
 
 // ============================================================================
@@ -96,14 +99,14 @@ export interface RegisterTicketParams {
   /** Event PDA address (base58 string) */
   eventPda: string;
   
-  /** Database ticket ID (u64) */
-  ticketId: number | bigint;
-  
   /** Compressed NFT asset ID (base58 string) */
   nftAssetId: string;
   
-  /** Owner's user ID from database (max 64 chars) */
+  /** Owner's user ID from database; only its salted commitment goes on-chain */
   ownerId: string;
+  
+  /** Owner's 32-byte salt, stored with the ticket in the database */
+  ownerSalt: Uint8Array;
 }
 
 /**
@@ -116,8 +119,17 @@ export interface TransferTicketParams {
   /** Event PDA address (base58 string) */
   eventPda: string;
   
-  /** New owner's user ID from database (max 64 chars) */
+  /** New owner's user ID from database; only its salted commitment goes on-chain */
   newOwnerId: string;
+  
+  /** New owner's 32-byte salt, stored with the ticket in the database */
+  newOwnerSalt: Uint8Array;
+  
+  /** Wallet to move the cNFT to (default: stays in the current wallet) */
+  newLeafOwner?: string;
+  
+  /** Resale price in lamports, 0 for a gift (default: 0) */
+  salePrice?: number | bigint;
 }
 
 /**
@@ -129,6 +141,9 @@ export interface VerifyTicketParams {
   
   /** Event PDA address (base58 string) */
   eventPda: string;
+  
+  /** Gate or zone scanning the ticket (max 16 bytes) */
+  gate: string;
 }
 
 // ============================================================================
@@ -159,7 +174,7 @@ export interface RegisterTicketResult {
   /** Transaction signature (base58 string) */
   signature: string;
   
-  /** Ticket ID that was used */
+  /** On-chain ticket number, read from the cNFT leaf */
   ticketId: number | bigint;
 }
 
@@ -191,8 +206,11 @@ export interface TicketInfo {
   /** NFT asset ID */
   nftAssetId: string;
   
-  /** Current owner's user ID */
-  currentOwnerId: string;
+  /** Salted commitment to the owner's user ID; check with matchesOwnerCommitment */
+  ownerCommitment: Buffer;
+  
+  /** Wallet holding the cNFT, the platform wallet if custodial */
+  ownerWallet: string;
   
   /** Whether ticket has been used at door */
   used: boolean;
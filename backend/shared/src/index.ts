@@ -215,6 +215,14 @@ export {
   InvalidRoyaltyError,
 } from './blockchain/types';
 
+// Owner commitment helpers
+export {
+  OWNER_SALT_LENGTH,
+  generateOwnerSalt,
+  deriveOwnerCommitment,
+  matchesOwnerCommitment,
+} from './blockchain/commitment';
+
 // PDA derivation helpers
 export {
   derivePlatformPDA,
@@ -223,6 +231,9 @@ export {
   deriveTicketPDA,
   deriveReentrancyGuardPDA,
   deriveListingReentrancyGuardPDA,
+  deriveRoleRegistryPDA,
+  deriveEventTreePDA,
+  deriveTreeConfigPDA,
   toBase58,
   fromBase58,
 } from './blockchain/pda';
@@ -11,6 +11,17 @@ pub const TREE_CAPACITY: u32 = 16_384;        // 2^14
 pub const PLATFORM_SEED: &[u8] = b"platform";
 pub const VENUE_SEED: &[u8] = b"venue";
 pub const EVENT_SEED: &[u8] = b"event";
+pub const TREE_CREATOR_SEED: &[u8] = b"tree_creator";
+pub const EVENT_TREE_SEED: &[u8] = b"event_tree";
+pub const COLLECTION_MINT_SEED: &[u8] = b"collection_mint";
+pub const COLLECTION_AUTHORITY_SEED: &[u8] = b"collection_authority";
+pub const SEAT_MAP_SEED: &[u8] = b"seat_map";
+pub const TICKET_TIER_SEED: &[u8] = b"ticket_tier";
+pub const EVENT_VAULT_SEED: &[u8] = b"event_vault";
+pub const SETTLEMENT_SEED: &[u8] = b"settlement";
+pub const ROLE_REGISTRY_SEED: &[u8] = b"role_registry";
+pub const ADMIN_OPERATION_SEED: &[u8] = b"admin_op";
+pub const VENUE_STAFF_SEED: &[u8] = b"venue_staff";
 
 // Financial constants
 pub const MIN_TICKET_PRICE: u64 = 100_000;                // 0.0001 SOL
@@ -21,8 +32,42 @@ pub const RESALE_PRICE_CAP_MULTIPLIER: u16 = 110;        // 110% max markup
 // Compressed NFT tree configuration
 pub const TREE_MAX_DEPTH: u8 = 14;           // 16,384 tickets
 pub const TREE_MAX_BUFFER_SIZE: u32 = 256;   // Concurrent operations
-pub const TREE_CANOPY_DEPTH: u8 = 17;        // Optimized for proof size
+pub const TREE_CANOPY_DEPTH: u8 = 10;        // Proofs need 4 nodes at depth 14
+pub const TREE_MAX_CANOPY_DEPTH: u8 = 17;    // Account compression limit
+pub const MAX_TREES_PER_EVENT: u8 = 16;      // Rollover trees per event
+pub const MAX_TREE_CAPACITY: u32 = 65_536;    // 8 KiB refund bitmap per tree
 pub const MAX_TICKET_PURCHASE: u8 = 10;
 
+// Seat inventory
+pub const MAX_SECTION_NAME: usize = 16;
+pub const MAX_SEATS_PER_SECTION: u32 = 65_536;  // 8 KiB bitmap
+
+// Refund schedule
+pub const MAX_REFUND_STEPS: usize = 4;
+
+// Check-in
+pub const MAX_GATE_NAME: usize = 16;
+pub const MAX_CHECKIN_OFFSET: i64 = 24 * 3600;  // Doors/close offsets up to a day
+
+// Ticket tiers
+pub const MAX_TIER_NAME: usize = 16;
+pub const MAX_TIERS_PER_EVENT: u8 = 8;
+
+// Bubblegum metadata limits
+pub const CNFT_MAX_NAME_LENGTH: usize = 32;
+pub const CNFT_MAX_URI_LENGTH: usize = 200;
+pub const CNFT_SYMBOL: &str = "TKT";
+
 // Cross-program IDs
 pub const MARKETPLACE_PROGRAM_ID: &str = "MKT2222222222222222222222222222222222222222";
+
+// Admin roles
+pub const MAX_ROLE_MEMBERS: usize = 32;
+
+// Admin timelock
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 2 * 24 * 60 * 60;   // 48 hours notice
+pub const MIN_TIMELOCK_DELAY: i64 = 60 * 60;                // 1 hour
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;      // 30 days
+
+// Venue payouts
+pub const MAX_PAYOUT_WALLETS: usize = 3;    // Artist + 3 + platform = Bubblegum's 5 creators
@@ -101,6 +101,191 @@ pub enum TicketTokenError {
     #[msg("Ticket transfer not allowed for this event")]
     TransferNotAllowed,
     
-    #[msg("Owner ID exceeds maximum length")]
-    OwnerIdTooLong,
+    // Compressed NFT tree errors
+    #[msg("Merkle tree not initialized for this event")]
+    TreeNotInitialized,
+    
+    #[msg("Merkle tree does not belong to this event")]
+    InvalidMerkleTree,
+    
+    #[msg("Event already has the maximum number of trees")]
+    TooManyTrees,
+    
+    #[msg("No free leaves left in the event's trees")]
+    TreeFull,
+    
+    // Collection errors
+    #[msg("Collection NFT not created for this event")]
+    CollectionNotInitialized,
+    
+    #[msg("Collection NFT already created for this event")]
+    CollectionAlreadyInitialized,
+    
+    #[msg("Collection mint does not belong to this event")]
+    InvalidCollection,
+    
+    // Royalty errors
+    #[msg("Artist royalty requires an artist wallet")]
+    InvalidArtistWallet,
+    
+    #[msg("Royalty recipients must be distinct wallets")]
+    DuplicateRoyaltyRecipient,
+    
+    #[msg("Royalty cut too small to earn a creator share")]
+    RoyaltyShareTooSmall,
+    
+    // Seat inventory errors
+    #[msg("Invalid seat map section name or dimensions")]
+    InvalidSeatMap,
+    
+    #[msg("Seat does not exist in this section")]
+    InvalidSeat,
+    
+    #[msg("Seat has already been sold")]
+    SeatAlreadySold,
+    
+    // Ticket tier errors
+    #[msg("Event already has the maximum number of tiers")]
+    TooManyTiers,
+    
+    #[msg("Invalid tier name or sale window")]
+    InvalidTier,
+    
+    #[msg("No tickets left in this tier")]
+    TierSoldOut,
+    
+    #[msg("Tier sale has not started yet")]
+    TierSaleNotStarted,
+    
+    #[msg("Tier sale has ended")]
+    TierSaleEnded,
+    
+    // Cancellation/refund errors
+    #[msg("Event has been cancelled")]
+    EventCancelled,
+    
+    #[msg("Event has not been cancelled")]
+    EventNotCancelled,
+    
+    #[msg("Event vault cannot cover this payout")]
+    InsufficientVaultBalance,
+    
+    #[msg("Ticket metadata does not match this ticket")]
+    InvalidTicketMetadata,
+    
+    #[msg("Refund window has closed")]
+    RefundWindowClosed,
+    
+    #[msg("Invalid refund schedule")]
+    InvalidRefundSchedule,
+    
+    // Settlement errors
+    #[msg("Event has not ended yet")]
+    EventNotEnded,
+    
+    #[msg("Event has already been settled")]
+    EventAlreadySettled,
+    
+    #[msg("Settlement is on hold pending a dispute")]
+    SettlementOnHold,
+    
+    // Pause errors
+    #[msg("Platform is paused")]
+    PlatformPaused,
+    
+    #[msg("Platform is not paused")]
+    PlatformNotPaused,
+    
+    // Ownership errors
+    #[msg("No platform owner transfer is pending")]
+    NoPendingOwner,
+    
+    #[msg("Signer is not the proposed platform owner")]
+    NotPendingOwner,
+    
+    #[msg("Invalid proposed platform owner")]
+    InvalidNewOwner,
+    
+    // Role errors
+    #[msg("Role already granted to this key")]
+    RoleAlreadyGranted,
+    
+    #[msg("Key does not hold this role")]
+    RoleNotGranted,
+    
+    #[msg("Role registry is full")]
+    TooManyRoleMembers,
+    
+    // Admin timelock errors
+    #[msg("Timelock delay out of range")]
+    InvalidTimelockDelay,
+    
+    #[msg("Admin operation is still timelocked")]
+    OperationNotReady,
+    
+    #[msg("Admin operation already executed or cancelled")]
+    OperationNotPending,
+    
+    // Venue lifecycle errors
+    #[msg("Revocation reason code is required")]
+    InvalidRevocationReason,
+    
+    #[msg("Venue was suspended by a platform admin")]
+    VenueSuspended,
+    
+    // Venue payout errors
+    #[msg("Payout wallets must be unique, non-empty and weigh 100% in total")]
+    InvalidPayoutWallets,
+    
+    #[msg("Payout wallet accounts do not match the venue's payout wallets")]
+    PayoutWalletMismatch,
+    
+    // Venue staff errors
+    #[msg("Invalid staff permissions or expiry")]
+    InvalidStaffPermissions,
+    
+    // Check-in errors
+    #[msg("Signer is not an authorized scanner for this venue")]
+    UnauthorizedScanner,
+    
+    #[msg("Check-in has not opened for this event")]
+    CheckInNotOpen,
+    
+    #[msg("Check-in has closed for this event")]
+    CheckInClosed,
+    
+    #[msg("Check-in window offsets out of range")]
+    InvalidCheckInWindow,
+    
+    // Ticket registry errors
+    #[msg("Ticket number has not been sold for this event")]
+    TicketNotSold,
+    
+    #[msg("Ticket accounts and owner commitments must be omitted or match the order quantity")]
+    InvalidTicketAccounts,
+    
+    #[msg("Ticket was refunded and can't be registered")]
+    TicketRefunded,
+    
+    // Ticket custody errors
+    #[msg("Signer does not hold this ticket")]
+    NotTicketHolder,
+    
+    #[msg("Tickets need an owner wallet")]
+    InvalidOwnerWallet,
+    
+    #[msg("Ticket holder must sign check-in")]
+    HolderSignatureRequired,
+    
+    // Provenance errors
+    #[msg("Transfer history does not match the ticket's provenance chain")]
+    InvalidProvenance,
+    
+    // Section pricing errors
+    #[msg("This tier does not sell seats in this section")]
+    SectionNotInTier,
+    
+    // Refund registry errors
+    #[msg("Rent payer does not match the ticket registry entry")]
+    InvalidRentPayer,
 }
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::Platform;
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AcceptPlatformOwner<'info> {
+    pub new_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = platform.pending_owner != Pubkey::default() @ TicketTokenError::NoPendingOwner,
+        constraint = platform.pending_owner == new_owner.key() @ TicketTokenError::NotPendingOwner,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+pub fn accept_platform_owner(ctx: Context<AcceptPlatformOwner>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+    let previous_owner = platform.owner;
+    platform.owner = ctx.accounts.new_owner.key();
+    platform.pending_owner = Pubkey::default();
+
+    emit!(PlatformOwnerTransferred {
+        previous_owner,
+        new_owner: platform.owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Platform owner changed from {} to {}", previous_owner, platform.owner);
+
+    Ok(())
+}
+
+#[event]
+pub struct PlatformOwnerTransferred {
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
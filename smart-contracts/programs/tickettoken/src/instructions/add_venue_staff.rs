@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, VenueStaff};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(staff: Pubkey)]
+pub struct AddVenueStaff<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        constraint = venue.owner == owner.key() @ TicketTokenError::UnauthorizedVenue,
+    )]
+    pub venue: Account<'info, Venue>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VenueStaff::SIZE,
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), staff.as_ref()],
+        bump
+    )]
+    pub venue_staff: Account<'info, VenueStaff>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_venue_staff(
+    ctx: Context<AddVenueStaff>,
+    staff: Pubkey,
+    permissions: u8,
+    expires_at: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        permissions != 0
+            && permissions & !VenueStaff::ALL_PERMISSIONS == 0
+            && (expires_at == 0 || expires_at > current_time),
+        TicketTokenError::InvalidStaffPermissions
+    );
+    
+    let venue_staff = &mut ctx.accounts.venue_staff;
+    venue_staff.venue = ctx.accounts.venue.key();
+    venue_staff.staff = staff;
+    venue_staff.permissions = permissions;
+    venue_staff.expires_at = expires_at;
+    venue_staff.added_at = current_time;
+    venue_staff.bump = ctx.bumps.venue_staff;
+    
+    emit!(VenueStaffAdded {
+        venue: venue_staff.venue,
+        staff,
+        permissions,
+        expires_at,
+        timestamp: current_time,
+    });
+    
+    msg!("Staff {} added to venue {} with permissions {:#06b}", staff, venue_staff.venue, permissions);
+    
+    Ok(())
+}
+
+#[event]
+pub struct VenueStaffAdded {
+    pub venue: Pubkey,
+    pub staff: Pubkey,
+    pub permissions: u8,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
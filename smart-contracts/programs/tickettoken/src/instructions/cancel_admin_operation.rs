@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, AdminOperation};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelAdminOperation<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        mut,
+        seeds = [ADMIN_OPERATION_SEED, admin_operation.id.to_le_bytes().as_ref()],
+        bump = admin_operation.bump,
+        constraint = admin_operation.is_pending() @ TicketTokenError::OperationNotPending,
+    )]
+    pub admin_operation: Account<'info, AdminOperation>,
+}
+
+pub fn cancel_admin_operation(ctx: Context<CancelAdminOperation>) -> Result<()> {
+    // The proposer can withdraw their own change; the owner can veto any
+    let authority = ctx.accounts.authority.key();
+    let operation = &mut ctx.accounts.admin_operation;
+    require!(
+        authority == operation.proposer || authority == ctx.accounts.platform.owner,
+        TicketTokenError::Unauthorized
+    );
+    operation.cancelled = true;
+
+    emit!(AdminOperationCancelled {
+        id: operation.id,
+        cancelled_by: authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Admin operation {} cancelled", operation.id);
+
+    Ok(())
+}
+
+#[event]
+pub struct AdminOperationCancelled {
+    pub id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, Event};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelEvent<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub venue: Account<'info, Venue>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_SEED,
+            venue.key().as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        constraint = !event.cancelled @ TicketTokenError::EventCancelled,
+        constraint = !event.settled @ TicketTokenError::EventAlreadySettled,
+    )]
+    pub event: Account<'info, Event>,
+}
+
+pub fn cancel_event(ctx: Context<CancelEvent>) -> Result<()> {
+    // Either the venue or the platform can call off a show
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == ctx.accounts.venue.owner || authority == ctx.accounts.platform.owner,
+        TicketTokenError::Unauthorized
+    );
+
+    // A show that has started is settled, not cancelled; otherwise the venue
+    // could cancel after the fact and turn sold tickets into refunds
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time < ctx.accounts.event.start_time,
+        TicketTokenError::EventAlreadyStarted
+    );
+
+    // Claims follow the refund policy in force now, not whatever the
+    // platform flag is later set to
+    let refund_fee_on_cancel = ctx.accounts.platform.refund_fee_on_cancel;
+    let event = &mut ctx.accounts.event;
+    event.cancelled = true;
+    event.refund_fee_on_cancel = refund_fee_on_cancel;
+
+    emit!(EventCancelled {
+        event: event.key(),
+        venue: event.venue,
+        cancelled_by: authority,
+        tickets_sold: event.tickets_sold,
+        refund_fee_on_cancel,
+        timestamp: current_time,
+    });
+
+    msg!("Event {} cancelled; {} tickets eligible for refund", event.key(), event.tickets_sold);
+
+    Ok(())
+}
+
+#[event]
+pub struct EventCancelled {
+    pub event: Pubkey,
+    pub venue: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub tickets_sold: u32,
+    pub refund_fee_on_cancel: bool,
+    pub timestamp: i64,
+}
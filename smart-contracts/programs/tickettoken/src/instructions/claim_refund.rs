@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Event, EventTree, EventVault, Ticket, TicketTier, TicketLeafArgs, TicketLeafMetadata};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::calculate_fee;
+use crate::utils::merkle::{burn_ticket, ticket_leaf_metadata, TicketBurnAccounts};
+
+#[derive(Accounts)]
+#[instruction(leaf: TicketLeafArgs)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        seeds = [
+            EVENT_SEED,
+            event.venue.as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.cancelled @ TicketTokenError::EventNotCancelled,
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [EVENT_VAULT_SEED, event.key().as_ref()],
+        bump = event_vault.bump,
+    )]
+    pub event_vault: Account<'info, EventVault>,
+
+    #[account(
+        seeds = [
+            TICKET_TIER_SEED,
+            event.key().as_ref(),
+            &[leaf.tier_index]
+        ],
+        bump = tier.bump,
+    )]
+    pub tier: Account<'info, TicketTier>,
+
+    /// CHECK: The ticket's registry PDA, always passed. Closed if it was
+    /// registered, and only its current owner can claim.
+    #[account(
+        mut,
+        seeds = [
+            b"ticket",
+            event.key().as_ref(),
+            (leaf.ticket_number as u64).to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub ticket: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the registry PDA's rent; must match `ticket.rent_payer`
+    #[account(mut)]
+    pub ticket_rent_payer: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_TREE_SEED,
+            event.key().as_ref(),
+            &[leaf.tree_index]
+        ],
+        bump = event_tree.bump,
+    )]
+    pub event_tree: Account<'info, EventTree>,
+
+    /// CHECK: Bubblegum tree config, validated by Bubblegum during the burn
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Tree holding the ticket leaf
+    #[account(
+        mut,
+        address = event_tree.merkle_tree @ TicketTokenError::InvalidMerkleTree,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program used by Bubblegum for leaf logs
+    #[account(address = spl_noop::ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = spl_account_compression::ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_refund<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimRefund<'info>>,
+    leaf: TicketLeafArgs,
+    metadata: TicketLeafMetadata,
+) -> Result<()> {
+    let event = &ctx.accounts.event;
+    // Claims stay open for as long as anything is left to claim
+    let current_time = Clock::get()?.unix_timestamp;
+    let metadata = ticket_leaf_metadata(event, &metadata, &leaf)?;
+
+    // Burning the leaf proves ownership and stops a second claim
+    burn_ticket(
+        &TicketBurnAccounts {
+            bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+            tree_config: &ctx.accounts.tree_config.to_account_info(),
+            merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+            leaf_owner: &ctx.accounts.holder.to_account_info(),
+            log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: &ctx.accounts.compression_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        &metadata,
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+    ctx.accounts.event_tree.mark_refunded(leaf.nonce)?;
+    // Scanned tickets still get their money back when the event is called off
+    Ticket::close_refunded(
+        &ctx.accounts.ticket.to_account_info(),
+        &ctx.accounts.holder.key(),
+        ctx.accounts.ticket_rent_payer.as_ref().map(|payer| payer.as_ref()),
+    )?;
+
+    // Full ticket price back; the platform's cut only if the policy at
+    // cancellation allowed it
+    let price = ctx.accounts.tier.price;
+    let fee = calculate_fee(price, ctx.accounts.event.platform_fee_bps)?
+        .min(ctx.accounts.event_vault.fees_held());
+    let (amount, fee_refunded) = if ctx.accounts.event.refund_fee_on_cancel {
+        (price, fee)
+    } else {
+        (price - fee, 0)
+    };
+
+    ctx.accounts.event_vault.record_refund(amount, fee_refunded)?;
+    ctx.accounts.platform.record_refund(1, amount, fee_refunded)?;
+    EventVault::pay_out(
+        &ctx.accounts.event_vault.to_account_info(),
+        &ctx.accounts.holder.to_account_info(),
+        amount,
+    )?;
+
+    emit!(RefundClaimed {
+        event: event.key(),
+        holder: ctx.accounts.holder.key(),
+        tier_index: leaf.tier_index,
+        ticket_number: leaf.ticket_number,
+        amount,
+        fee_refunded,
+        timestamp: current_time,
+    });
+
+    msg!("Refunded {} lamports for ticket #{}", amount, leaf.ticket_number);
+
+    Ok(())
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub event: Pubkey,
+    pub holder: Pubkey,
+    pub tier_index: u8,
+    pub ticket_number: u32,
+    pub amount: u64,
+    pub fee_refunded: u64,
+    pub timestamp: i64,
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::state::{Venue, Event, CreateEventParams, TreeConfig};
+use crate::state::{Platform, Venue, Event, EventVault, CreateEventParams, VenueStaff};
+use crate::constants::{EVENT_VAULT_SEED, VENUE_STAFF_SEED};
 use crate::errors::TicketTokenError;
 use crate::utils::{string_to_bytes, validate_string};
 use crate::utils::validation::*;
+use crate::utils::royalties::calculate_royalties;
 use crate::utils::reentrancy::{ReentrancyGuard};
 
 #[derive(Accounts)]
@@ -13,12 +15,26 @@ pub struct CreateEvent<'info> {
     
     #[account(
         mut,
-        constraint = authority.key() == venue.owner @ TicketTokenError::UnauthorizedVenue,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
         constraint = venue.verified @ TicketTokenError::VenueNotVerified,
         constraint = venue.active @ TicketTokenError::VenueInactive,
     )]
     pub venue: Account<'info, Venue>,
     
+    /// Staff record, required when the signer is not the venue owner
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+    
     #[account(
         init,
         payer = authority,
@@ -44,31 +60,50 @@ pub struct CreateEvent<'info> {
     )]
     pub reentrancy_guard: Account<'info, ReentrancyGuard>,
 
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            EVENT_VAULT_SEED,
+            event.key().as_ref()
+        ],
+        bump,
+        space = 8 + EventVault::SIZE,
+    )]
+    pub event_vault: Account<'info, EventVault>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn create_event(ctx: Context<CreateEvent>, params: CreateEventParams) -> Result<()> {
     let current_time = Clock::get()?.unix_timestamp;
+    VenueStaff::authorize(
+        &ctx.accounts.venue,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.venue_staff.as_deref(),
+        VenueStaff::CREATE_EVENTS,
+        current_time,
+    )?;
     
     // Validate all parameters
     validate_string(&params.name)?;
     validate_event_times(params.start_time, params.end_time, current_time)?;
+    // Only a list price now; each tier validates its own price
     validate_price_bounds(params.ticket_price)?;
     validate_capacity(params.total_tickets)?;
     validate_refund_window(params.refund_window)?;
+    validate_checkin_window(params.doors_open, params.checkin_close)?;
+    Event::validate_refund_schedule(&params.refund_schedule)?;
     
-    // Validate royalty percentages (basis points - 10000 = 100%)
-    require!(
-        params.artist_percentage + params.venue_percentage <= 10000,
-        TicketTokenError::InvalidRoyaltyPercentage
-    );
-    
-    // Validate tree capacity
-    let tree_config = TreeConfig::optimal();
-    require!(
-        tree_config.capacity() >= params.total_tickets,
-        TicketTokenError::InvalidCapacity
-    );
+    // Validate that the royalty config yields valid cNFT creator shares
+    calculate_royalties(
+        params.artist_wallet,
+        params.artist_percentage,
+        &ctx.accounts.venue.payout_wallets(),
+        params.venue_percentage,
+        ctx.accounts.platform.treasury,
+        ctx.accounts.platform.fee_bps,
+    )?;
     
     // Initialize event state
     let event = &mut ctx.accounts.event;
@@ -84,6 +119,11 @@ pub fn create_event(ctx: Context<CreateEvent>, params: CreateEventParams) -> Res
     event.start_time = params.start_time;
     event.end_time = params.end_time;
     event.refund_window = params.refund_window;
+    event.doors_open = params.doors_open;
+    event.checkin_close = params.checkin_close;
+    event.self_custody = params.self_custody;
+    event.refund_steps = params.refund_schedule.len() as u8;
+    event.refund_schedule[..params.refund_schedule.len()].copy_from_slice(&params.refund_schedule);
     event.metadata_uri = string_to_bytes(&params.metadata_uri, 64)?
         .try_into()
         .map_err(|_| TicketTokenError::UriTooLong)?;
@@ -94,9 +134,24 @@ pub fn create_event(ctx: Context<CreateEvent>, params: CreateEventParams) -> Res
     event.transferable = params.transferable;
     event.resaleable = params.resaleable;
     
-    // For now, we'll store a placeholder merkle tree pubkey
-    // Real merkle tree initialization will be added in the next step
+    // Set by initialize_event_tree; sales stay closed until then.
+    // Large events can add more trees and roll over when one fills up.
     event.merkle_tree = Pubkey::default();
+    event.tree_count = 0;
+    event.active_tree = 0;
+    
+    // Set by create_event_collection; ticket cNFTs are verified against it
+    event.collection_mint = Pubkey::default();
+    
+    // Price classes are added with create_ticket_tier
+    event.tier_count = 0;
+    event.cancelled = false;
+    event.next_ticket_number = 0;
+    event.settled = false;
+    event.dispute_hold = false;
+    
+    // Later fee changes only apply to events created after them
+    event.platform_fee_bps = ctx.accounts.platform.fee_bps;
     
     // Store royalty information (immutable)
     event.artist_wallet = params.artist_wallet;
@@ -108,11 +163,17 @@ pub fn create_event(ctx: Context<CreateEvent>, params: CreateEventParams) -> Res
     // Update venue event counter
     let venue = &mut ctx.accounts.venue;
     venue.event_count += 1;
+    ctx.accounts.platform.record_event()?;
     
     // Initialize reentrancy guard
     let reentrancy_guard = &mut ctx.accounts.reentrancy_guard;
     reentrancy_guard.is_locked = false;
     reentrancy_guard.bump = ctx.bumps.reentrancy_guard;
+    
+    // Primary-sale proceeds are escrowed here until settlement
+    let event_vault = &mut ctx.accounts.event_vault;
+    event_vault.event = ctx.accounts.event.key();
+    event_vault.bump = ctx.bumps.event_vault;
 
     emit!(EventCreated {
         venue: venue.key(),
@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::mpl_token_metadata::types::{CollectionDetails, DataV2};
+use anchor_spl::metadata::{
+    create_master_edition_v3, create_metadata_accounts_v3, CreateMasterEditionV3,
+    CreateMetadataAccountsV3, Metadata,
+};
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+use crate::state::{Platform, Venue, Event, VenueStaff};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::bytes_to_string;
+
+#[derive(Accounts)]
+pub struct CreateEventCollection<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub venue: Account<'info, Venue>,
+
+    /// Staff record, required when the signer is not the venue owner
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_SEED,
+            venue.key().as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        constraint = event.collection_mint == Pubkey::default() @ TicketTokenError::CollectionAlreadyInitialized,
+    )]
+    pub event: Account<'info, Event>,
+
+    /// CHECK: Program PDA that owns and verifies the event collection
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED, event.key().as_ref()],
+        bump,
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COLLECTION_MINT_SEED, event.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = collection_authority,
+        mint::freeze_authority = collection_authority,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = collection_authority,
+    )]
+    pub collection_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Collection metadata, created by Token Metadata
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            collection_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition, created by Token Metadata
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            collection_mint.key().as_ref(),
+            b"edition"
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub collection_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn create_event_collection(ctx: Context<CreateEventCollection>) -> Result<()> {
+    VenueStaff::authorize(
+        &ctx.accounts.venue,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.venue_staff.as_deref(),
+        VenueStaff::EDIT_EVENTS,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let event_key = ctx.accounts.event.key();
+    let collection_authority_bump = [ctx.bumps.collection_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        COLLECTION_AUTHORITY_SEED,
+        event_key.as_ref(),
+        &collection_authority_bump,
+    ]];
+
+    // A collection is a regular 1/1 NFT held by the collection authority
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                to: ctx.accounts.collection_token_account.to_account_info(),
+                authority: ctx.accounts.collection_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    let event = &ctx.accounts.event;
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                mint_authority: ctx.accounts.collection_authority.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                update_authority: ctx.accounts.collection_authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name: bytes_to_string(&event.name),
+            symbol: CNFT_SYMBOL.to_string(),
+            uri: bytes_to_string(&event.metadata_uri),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        // Sized so Bubblegum keeps the collection's ticket count up to date
+        Some(CollectionDetails::V1 { size: 0 }),
+    )?;
+
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: ctx.accounts.collection_edition.to_account_info(),
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                update_authority: ctx.accounts.collection_authority.to_account_info(),
+                mint_authority: ctx.accounts.collection_authority.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                metadata: ctx.accounts.collection_metadata.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )?;
+
+    let collection_mint_key = ctx.accounts.collection_mint.key();
+    ctx.accounts.event.collection_mint = collection_mint_key;
+
+    emit!(EventCollectionCreated {
+        event: event_key,
+        collection_mint: collection_mint_key,
+        collection_authority: ctx.accounts.collection_authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Collection {} created for event {}", collection_mint_key, event_key);
+
+    Ok(())
+}
+
+#[event]
+pub struct EventCollectionCreated {
+    pub event: Pubkey,
+    pub collection_mint: Pubkey,
+    pub collection_authority: Pubkey,
+    pub timestamp: i64,
+}
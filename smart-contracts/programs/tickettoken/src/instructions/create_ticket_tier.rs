@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, Event, TicketTier, CreateTicketTierParams, VenueStaff};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::{string_to_bytes, validate_string};
+use crate::utils::validation::validate_price_bounds;
+
+#[derive(Accounts)]
+pub struct CreateTicketTier<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub venue: Account<'info, Venue>,
+
+    /// Staff record, required when the signer is not the venue owner
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_SEED,
+            venue.key().as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        constraint = event.tier_count < MAX_TIERS_PER_EVENT @ TicketTokenError::TooManyTiers,
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            TICKET_TIER_SEED,
+            event.key().as_ref(),
+            &[event.tier_count]
+        ],
+        bump,
+        space = 8 + TicketTier::SIZE,
+    )]
+    pub tier: Account<'info, TicketTier>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_ticket_tier(ctx: Context<CreateTicketTier>, params: CreateTicketTierParams) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    VenueStaff::authorize(
+        &ctx.accounts.venue,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.venue_staff.as_deref(),
+        VenueStaff::EDIT_EVENTS,
+        current_time,
+    )?;
+
+    let event = &ctx.accounts.event;
+
+    require!(
+        current_time < event.start_time,
+        TicketTokenError::EventAlreadyStarted
+    );
+
+    validate_string(&params.name)?;
+    require!(
+        !params.name.is_empty() && params.name.len() <= MAX_TIER_NAME,
+        TicketTokenError::InvalidTier
+    );
+    validate_price_bounds(params.price)?;
+    require!(
+        params.capacity > 0 && params.capacity <= event.total_tickets,
+        TicketTokenError::InvalidCapacity
+    );
+    require!(
+        params.sale_start >= 0 && params.sale_end >= 0,
+        TicketTokenError::InvalidTier
+    );
+    require!(
+        params.sale_start == 0 || params.sale_end == 0 || params.sale_start < params.sale_end,
+        TicketTokenError::InvalidTier
+    );
+
+    let event_key = event.key();
+    let index = event.tier_count;
+
+    let tier = &mut ctx.accounts.tier;
+    tier.event = event_key;
+    tier.index = index;
+    tier.name = string_to_bytes(&params.name, MAX_TIER_NAME)?
+        .try_into()
+        .map_err(|_| TicketTokenError::InvalidTier)?;
+    tier.price = params.price;
+    tier.capacity = params.capacity;
+    tier.sold = 0;
+    tier.sale_start = params.sale_start;
+    tier.sale_end = params.sale_end;
+    tier.bump = ctx.bumps.tier;
+
+    ctx.accounts.event.tier_count = index + 1;
+
+    emit!(TicketTierCreated {
+        event: event_key,
+        index,
+        name: params.name.clone(),
+        price: params.price,
+        capacity: params.capacity,
+        sale_start: params.sale_start,
+        sale_end: params.sale_end,
+    });
+
+    msg!("Tier #{} {} created: {} tickets at {} lamports", index, params.name, params.capacity, params.price);
+
+    Ok(())
+}
+
+#[event]
+pub struct TicketTierCreated {
+    pub event: Pubkey,
+    pub index: u8,
+    pub name: String,
+    pub price: u64,
+    pub capacity: u32,
+    pub sale_start: i64,
+    pub sale_end: i64,
+}
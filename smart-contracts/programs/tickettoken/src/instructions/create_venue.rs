@@ -3,6 +3,7 @@ use crate::state::{Platform, Venue};
 use crate::errors::TicketTokenError;
 use crate::constants::*;
 use crate::utils::string_to_bytes;
+use crate::utils::validation::validate_venue_profile;
 
 #[derive(Accounts)]
 #[instruction(venue_id: String)]
@@ -13,7 +14,8 @@ pub struct CreateVenue<'info> {
     #[account(
         mut,
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
     )]
     pub platform: Account<'info, Platform>,
     
@@ -40,18 +42,7 @@ pub fn create_venue(
         venue_id.len() <= 32,
         TicketTokenError::VenueIdTooLong
     );
-    require!(
-        name.len() <= MAX_VENUE_NAME,
-        TicketTokenError::VenueNameTooLong
-    );
-    require!(
-        metadata_uri.len() <= MAX_URI_LENGTH,
-        TicketTokenError::UriTooLong
-    );
-    require!(
-        name.chars().all(|c| c.is_ascii_graphic() || c == ' '),
-        TicketTokenError::InvalidCharacters
-    );
+    validate_venue_profile(&name, &metadata_uri)?;
     
     // Convert strings to fixed byte arrays
     let venue_id_bytes = string_to_bytes(&venue_id, 32)?;
@@ -68,11 +59,13 @@ pub fn create_venue(
     venue.active = true;
     venue.event_count = 0;
     venue.total_sales = 0;
+    venue.revocation_reason = 0;
+    venue.payout_count = 0; // Proceeds go to the owner until set_venue_payout
     venue.bump = ctx.bumps.venue; // Store bump seed!
     
     // Increment platform venue counter
     let platform = &mut ctx.accounts.platform;
-    platform.total_venues += 1;
+    platform.record_venue()?;
     
     // Clone name for the event (or use &name in both places)
     emit!(VenueCreated {
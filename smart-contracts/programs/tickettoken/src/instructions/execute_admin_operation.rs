@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, AdminOperation, AdminOperationKind};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ExecuteAdminOperation<'info> {
+    /// Anyone can apply an operation once its delay has passed
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        mut,
+        seeds = [ADMIN_OPERATION_SEED, admin_operation.id.to_le_bytes().as_ref()],
+        bump = admin_operation.bump,
+        constraint = admin_operation.is_pending() @ TicketTokenError::OperationNotPending,
+    )]
+    pub admin_operation: Account<'info, AdminOperation>,
+}
+
+pub fn execute_admin_operation(ctx: Context<ExecuteAdminOperation>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let operation = &mut ctx.accounts.admin_operation;
+    require!(operation.is_ready(current_time), TicketTokenError::OperationNotReady);
+
+    operation.kind.apply(&mut ctx.accounts.platform)?;
+    operation.executed = true;
+
+    emit!(AdminOperationExecuted {
+        id: operation.id,
+        kind: operation.kind,
+        executed_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    msg!("Admin operation {} executed", operation.id);
+
+    Ok(())
+}
+
+#[event]
+pub struct AdminOperationExecuted {
+    pub id: u64,
+    pub kind: AdminOperationKind,
+    pub executed_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, PlatformStats};
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct GetPlatformStats<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+/// Read-only; Anchor hands the stats back through set_return_data so
+/// clients can simulate this instead of decoding the Platform account.
+pub fn get_platform_stats(ctx: Context<GetPlatformStats>) -> Result<PlatformStats> {
+    Ok(ctx.accounts.platform.stats())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Role, RoleRegistry};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ManageRole<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = platform.owner == owner.key() @ TicketTokenError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        mut,
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+}
+
+pub fn grant_role(ctx: Context<ManageRole>, member: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.role_registry.grant(member, role)?;
+
+    emit!(RoleGranted {
+        member,
+        role,
+        granted_by: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Granted {:?} to {}", role, member);
+
+    Ok(())
+}
+
+pub fn revoke_role(ctx: Context<ManageRole>, member: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.role_registry.revoke(&member, role)?;
+
+    emit!(RoleRevoked {
+        member,
+        role,
+        revoked_by: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Revoked {:?} from {}", role, member);
+
+    Ok(())
+}
+
+#[event]
+pub struct RoleGranted {
+    pub member: Pubkey,
+    pub role: Role,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub member: Pubkey,
+    pub role: Role,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use mpl_bubblegum::instructions::CreateTreeConfigCpiBuilder;
+use crate::state::{Platform, Venue, Event, EventTree, TreeConfig, VenueStaff};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(capacity: u32, max_proof_bytes: u16)]
+pub struct InitializeEventTree<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub venue: Account<'info, Venue>,
+
+    /// Staff record, required when the signer is not the venue owner
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_SEED,
+            venue.key().as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        constraint = event.tree_count < MAX_TREES_PER_EVENT @ TicketTokenError::TooManyTrees,
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            EVENT_TREE_SEED,
+            event.key().as_ref(),
+            &[event.tree_count]
+        ],
+        bump,
+        space = 8 + EventTree::size(TreeConfig::for_capacity(capacity, max_proof_bytes)?.capacity()),
+    )]
+    pub event_tree: Account<'info, EventTree>,
+
+    /// CHECK: Bubblegum tree config, initialized by Bubblegum
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// New tree account, allocated here and owned by account compression
+    #[account(mut)]
+    pub merkle_tree: Signer<'info>,
+
+    /// CHECK: Program PDA recorded as the tree creator
+    #[account(
+        seeds = [TREE_CREATOR_SEED, event.key().as_ref()],
+        bump,
+    )]
+    pub tree_creator: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program used by Bubblegum for leaf logs
+    #[account(address = spl_noop::ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = spl_account_compression::ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_event_tree(
+    ctx: Context<InitializeEventTree>,
+    capacity: u32,
+    max_proof_bytes: u16,
+) -> Result<()> {
+    VenueStaff::authorize(
+        &ctx.accounts.venue,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.venue_staff.as_deref(),
+        VenueStaff::EDIT_EVENTS,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    require!(
+        capacity <= ctx.accounts.event.total_tickets,
+        TicketTokenError::InvalidCapacity
+    );
+    let config = TreeConfig::for_capacity(capacity, max_proof_bytes)?;
+
+    // Allocate the tree account for the compression program
+    let space = config.account_size();
+    create_account(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.merkle_tree.to_account_info(),
+            },
+        ),
+        config.rent_required()?,
+        space as u64,
+        &spl_account_compression::ID,
+    )?;
+
+    // Initialize the tree through Bubblegum with our PDA as creator
+    let event_key = ctx.accounts.event.key();
+    let tree_creator_bump = [ctx.bumps.tree_creator];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        TREE_CREATOR_SEED,
+        event_key.as_ref(),
+        &tree_creator_bump,
+    ]];
+
+    CreateTreeConfigCpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+        .tree_config(&ctx.accounts.tree_config.to_account_info())
+        .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+        .payer(&ctx.accounts.authority.to_account_info())
+        .tree_creator(&ctx.accounts.tree_creator.to_account_info())
+        .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+        .compression_program(&ctx.accounts.compression_program.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .max_depth(config.max_depth as u32)
+        .max_buffer_size(config.max_buffer_size as u32)
+        .public(false)
+        .invoke_signed(signer_seeds)?;
+
+    let merkle_tree_key = ctx.accounts.merkle_tree.key();
+    let event = &mut ctx.accounts.event;
+    let index = event.tree_count;
+
+    let event_tree = &mut ctx.accounts.event_tree;
+    event_tree.event = event_key;
+    event_tree.index = index;
+    event_tree.merkle_tree = merkle_tree_key;
+    event_tree.capacity = config.capacity();
+    event_tree.minted = 0;
+    event_tree.first_ticket = 0;
+    event_tree.bump = ctx.bumps.event_tree;
+    event_tree.refunded = vec![0u8; EventTree::bitmap_len(config.capacity())];
+
+    // The first tree opens sales; later ones wait for rollover
+    if index == 0 {
+        event.merkle_tree = merkle_tree_key;
+    }
+    event.tree_count = index + 1;
+
+    emit!(EventTreeInitialized {
+        event: event_key,
+        index,
+        merkle_tree: merkle_tree_key,
+        max_depth: config.max_depth,
+        max_buffer_size: config.max_buffer_size,
+        canopy_depth: config.canopy_depth,
+        capacity: config.capacity(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Merkle tree #{} {} initialized for event {} ({} bytes)", index, merkle_tree_key, event_key, space);
+
+    Ok(())
+}
+
+#[event]
+pub struct EventTreeInitialized {
+    pub event: Pubkey,
+    pub index: u8,
+    pub merkle_tree: Pubkey,
+    pub max_depth: u8,
+    pub max_buffer_size: u16,
+    pub canopy_depth: u8,
+    pub capacity: u32,
+    pub timestamp: i64,
+}
@@ -43,6 +43,10 @@ pub fn initialize_platform(
     platform.paused = false;
     platform.bump = ctx.bumps.platform; // Store bump seed!
     platform.total_venues = 0;
+    platform.refund_fee_on_cancel = true;
+    platform.pending_owner = Pubkey::default();
+    platform.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+    platform.next_operation_id = 0;
     
     emit!(PlatformInitialized {
         owner: platform.owner,
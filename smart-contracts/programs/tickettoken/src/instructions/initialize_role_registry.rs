@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, RoleRegistry};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitializeRoleRegistry<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = platform.owner == owner.key() @ TicketTokenError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RoleRegistry::SIZE,
+        seeds = [ROLE_REGISTRY_SEED],
+        bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the admin role registry. The owner runs it once, right after
+/// initialize_platform on a fresh deployment; role-gated instructions
+/// fail until it exists.
+pub fn initialize_role_registry(ctx: Context<InitializeRoleRegistry>) -> Result<()> {
+    // Admin roles start empty; the owner grants them with grant_role
+    let role_registry = &mut ctx.accounts.role_registry;
+    role_registry.bump = ctx.bumps.role_registry;
+    role_registry.members = Vec::new();
+
+    emit!(RoleRegistryInitialized {
+        owner: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Role registry initialized");
+
+    Ok(())
+}
+
+#[event]
+pub struct RoleRegistryInitialized {
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
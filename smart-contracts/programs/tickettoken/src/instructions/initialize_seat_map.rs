@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, Event, SeatMap, VenueStaff};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::string_to_bytes;
+
+#[derive(Accounts)]
+#[instruction(section: String, rows: u16, seats_per_row: u16)]
+pub struct InitializeSeatMap<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub venue: Account<'info, Venue>,
+
+    /// Staff record, required when the signer is not the venue owner
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+
+    #[account(
+        seeds = [
+            EVENT_SEED,
+            venue.key().as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            SEAT_MAP_SEED,
+            event.key().as_ref(),
+            section.as_bytes()
+        ],
+        bump,
+        space = 8 + SeatMap::size(rows, seats_per_row),
+    )]
+    pub seat_map: Account<'info, SeatMap>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_seat_map(
+    ctx: Context<InitializeSeatMap>,
+    section: String,
+    rows: u16,
+    seats_per_row: u16,
+    tiers: u8,
+) -> Result<()> {
+    VenueStaff::authorize(
+        &ctx.accounts.venue,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.venue_staff.as_deref(),
+        VenueStaff::EDIT_EVENTS,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    SeatMap::validate_layout(&section, rows, seats_per_row)?;
+    require!(tiers != 0, TicketTokenError::InvalidSeatMap);
+
+    let seat_map = &mut ctx.accounts.seat_map;
+    seat_map.event = ctx.accounts.event.key();
+    seat_map.section = string_to_bytes(&section, MAX_SECTION_NAME)?
+        .try_into()
+        .map_err(|_| TicketTokenError::InvalidSeatMap)?;
+    seat_map.rows = rows;
+    seat_map.seats_per_row = seats_per_row;
+    seat_map.seats_sold = 0;
+    seat_map.tiers = tiers;
+    seat_map.bump = ctx.bumps.seat_map;
+    seat_map.sold = vec![0u8; SeatMap::bitmap_len(rows, seats_per_row)];
+
+    emit!(SeatMapInitialized {
+        event: seat_map.event,
+        seat_map: seat_map.key(),
+        section: section.clone(),
+        rows,
+        seats_per_row,
+        tiers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Seat map {} initialized: {} rows x {} seats", section, rows, seats_per_row);
+
+    Ok(())
+}
+
+#[event]
+pub struct SeatMapInitialized {
+    pub event: Pubkey,
+    pub seat_map: Pubkey,
+    pub section: String,
+    pub rows: u16,
+    pub seats_per_row: u16,
+    pub tiers: u8,
+    pub timestamp: i64,
+}
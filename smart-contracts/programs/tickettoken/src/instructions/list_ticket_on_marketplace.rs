@@ -3,20 +3,47 @@ use anchor_lang::solana_program::{
     instruction::{AccountMeta, Instruction},
     program::invoke,
 };
-use crate::state::{Event};
+use crate::state::{Platform, Event, Ticket, TicketTier};
 use crate::errors::TicketTokenError;
+use crate::constants::*;
 
 #[derive(Accounts)]
+#[instruction(ticket_asset_id: Pubkey)]
 pub struct ListTicketOnMarketplace<'info> {
     #[account(mut)]
     pub ticket_owner: Signer<'info>,
 
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+
     #[account(
         constraint = event.resaleable @ TicketTokenError::ResaleNotAllowed,
         constraint = Clock::get()?.unix_timestamp < event.start_time @ TicketTokenError::EventAlreadyStarted,
     )]
     pub event: Account<'info, Event>,
 
+    /// Registered ticket being listed; its tier sets the price cap
+    #[account(
+        seeds = [b"ticket", event.key().as_ref(), ticket.ticket_id.to_le_bytes().as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.nft_asset_id == ticket_asset_id @ TicketTokenError::InvalidTicket,
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        seeds = [
+            TICKET_TIER_SEED,
+            event.key().as_ref(),
+            &[ticket.tier_index]
+        ],
+        bump = tier.bump,
+    )]
+    pub tier: Account<'info, TicketTier>,
+
     /// CHECK: Marketplace program
     pub marketplace_program: UncheckedAccount<'info>,
 
@@ -41,10 +68,11 @@ pub fn list_ticket_on_marketplace(
     expires_at: i64,
 ) -> Result<()> {
     let event = &ctx.accounts.event;
+    let original_price = ctx.accounts.tier.price;
 
-    // Validate price cap (110% of original)
-    let max_price = event.ticket_price
-        .checked_mul(110)
+    // Validate price cap (110% of what the ticket's tier sold for)
+    let max_price = original_price
+        .checked_mul(RESALE_PRICE_CAP_MULTIPLIER as u64)
         .ok_or(TicketTokenError::MathOverflow)?
         .checked_div(100)
         .ok_or(TicketTokenError::MathOverflow)?;
@@ -68,7 +96,7 @@ pub fn list_ticket_on_marketplace(
     data.extend_from_slice(&[242, 93, 182, 110, 115, 127, 189, 59]); // placeholder
     data.extend_from_slice(&ticket_asset_id.to_bytes());
     data.extend_from_slice(&price.to_le_bytes());
-    data.extend_from_slice(&event.ticket_price.to_le_bytes());
+    data.extend_from_slice(&original_price.to_le_bytes());
     data.extend_from_slice(&expires_at.to_le_bytes());
 
     // Build accounts for CPI
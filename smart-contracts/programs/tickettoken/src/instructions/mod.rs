@@ -1,16 +1,70 @@
 pub mod initialize_platform;
+pub mod propose_platform_owner;
+pub mod accept_platform_owner;
+pub mod update_treasury;
+pub mod initialize_role_registry;
+pub mod grant_role;
+pub mod queue_admin_operation;
+pub mod execute_admin_operation;
+pub mod cancel_admin_operation;
+pub mod get_platform_stats;
 pub mod create_venue;
 pub mod verify_venue;
+pub mod update_venue;
+pub mod set_venue_active;
+pub mod revoke_venue_verification;
+pub mod set_venue_payout;
+pub mod add_venue_staff;
+pub mod remove_venue_staff;
 pub mod create_event;
+pub mod initialize_event_tree;
+pub mod create_event_collection;
+pub mod initialize_seat_map;
+pub mod create_ticket_tier;
+pub mod set_checkin_window;
+pub mod set_refund_policy;
+pub mod pause_platform;
+pub mod cancel_event;
+pub mod claim_refund;
+pub mod refund_ticket;
+pub mod set_dispute_hold;
+pub mod settle_event;
 pub mod purchase_tickets;
 pub mod register_ticket;
 pub mod transfer_ticket;
 pub mod verify_ticket;
 
 pub use initialize_platform::*;
+pub use propose_platform_owner::*;
+pub use accept_platform_owner::*;
+pub use update_treasury::*;
+pub use initialize_role_registry::*;
+pub use grant_role::*;
+pub use queue_admin_operation::*;
+pub use execute_admin_operation::*;
+pub use cancel_admin_operation::*;
+pub use get_platform_stats::*;
 pub use create_venue::*;
 pub use verify_venue::*;
+pub use update_venue::*;
+pub use set_venue_active::*;
+pub use revoke_venue_verification::*;
+pub use set_venue_payout::*;
+pub use add_venue_staff::*;
+pub use remove_venue_staff::*;
 pub use create_event::*;
+pub use initialize_event_tree::*;
+pub use create_event_collection::*;
+pub use initialize_seat_map::*;
+pub use create_ticket_tier::*;
+pub use set_checkin_window::*;
+pub use set_refund_policy::*;
+pub use pause_platform::*;
+pub use cancel_event::*;
+pub use claim_refund::*;
+pub use refund_ticket::*;
+pub use set_dispute_hold::*;
+pub use settle_event::*;
 pub use purchase_tickets::*;
 pub use register_ticket::*;
 pub use transfer_ticket::*;
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Role, RoleRegistry};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct PausePlatform<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+        constraint = role_registry.authorizes(&platform.owner, &authority.key(), Role::Pauser) @ TicketTokenError::Unauthorized,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+}
+
+/// Incident-response kill switch. Sales, transfers and admin writes stop;
+/// refunds and cancellations stay open so holders can get their money back.
+pub fn pause_platform(ctx: Context<PausePlatform>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+    require!(!platform.paused, TicketTokenError::PlatformPaused);
+    platform.paused = true;
+
+    emit!(PlatformPauseChanged {
+        paused: true,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Platform paused by {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
+
+pub fn unpause_platform(ctx: Context<PausePlatform>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+    require!(platform.paused, TicketTokenError::PlatformNotPaused);
+    platform.paused = false;
+
+    emit!(PlatformPauseChanged {
+        paused: false,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Platform unpaused by {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
+
+#[event]
+pub struct PlatformPauseChanged {
+    pub paused: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
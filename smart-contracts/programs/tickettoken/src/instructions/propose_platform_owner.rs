@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::Platform;
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ProposePlatformOwner<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = platform.owner == owner.key() @ TicketTokenError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+/// First step of an ownership handover. Nothing changes until the new
+/// owner signs accept_platform_owner, so a mistyped key can't lock us out.
+pub fn propose_platform_owner(ctx: Context<ProposePlatformOwner>, new_owner: Pubkey) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+    require!(
+        new_owner != Pubkey::default() && new_owner != platform.owner,
+        TicketTokenError::InvalidNewOwner
+    );
+    platform.pending_owner = new_owner;
+
+    emit!(PlatformOwnerProposed {
+        owner: platform.owner,
+        pending_owner: new_owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Platform owner transfer proposed to {}", new_owner);
+
+    Ok(())
+}
+
+pub fn cancel_platform_owner_transfer(ctx: Context<ProposePlatformOwner>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+    require!(platform.pending_owner != Pubkey::default(), TicketTokenError::NoPendingOwner);
+    let cancelled = platform.pending_owner;
+    platform.pending_owner = Pubkey::default();
+
+    emit!(PlatformOwnerTransferCancelled {
+        owner: platform.owner,
+        cancelled_owner: cancelled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Platform owner transfer to {} cancelled", cancelled);
+
+    Ok(())
+}
+
+#[event]
+pub struct PlatformOwnerProposed {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformOwnerTransferCancelled {
+    pub owner: Pubkey,
+    pub cancelled_owner: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::state::{Platform, Venue, Event, MintTicketArgs};
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::metadata::Metadata;
+use mpl_bubblegum::utils::get_asset_id;
+use crate::state::{Platform, Venue, Event, EventTree, EventVault, SeatMap, Ticket, TicketTier, MintTicketArgs};
 use crate::errors::TicketTokenError;
 use crate::constants::*;
 use crate::utils::{calculate_fee, safe_add, safe_mul};
-use crate::utils::merkle::create_ticket_metadata;
+use crate::utils::merkle::{create_ticket_metadata, mint_ticket, TicketMintAccounts};
+use crate::utils::royalties::calculate_royalties;
 use crate::utils::reentrancy::{ReentrancyGuard};
 
 #[derive(Accounts)]
@@ -13,8 +17,10 @@ pub struct PurchaseTickets<'info> {
     pub buyer: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [b"platform"],
         bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
     )]
     pub platform: Account<'info, Platform>,
 
@@ -34,19 +40,19 @@ pub struct PurchaseTickets<'info> {
         ],
         bump = event.bump,
         constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        constraint = !event.cancelled @ TicketTokenError::EventCancelled,
+        constraint = event.merkle_tree != Pubkey::default() @ TicketTokenError::TreeNotInitialized,
+        constraint = event.collection_mint != Pubkey::default() @ TicketTokenError::CollectionNotInitialized,
     )]
     pub event: Account<'info, Event>,
 
-    /// CHECK: Venue treasury receives funds
-    #[account(mut)]
-    pub venue_treasury: UncheckedAccount<'info>,
-
-    /// CHECK: Platform treasury receives fees
+    /// Escrows the sale until settlement or cancellation
     #[account(
         mut,
-        constraint = platform_treasury.key() == platform.treasury @ TicketTokenError::InvalidTreasury
+        seeds = [EVENT_VAULT_SEED, event.key().as_ref()],
+        bump = event_vault.bump,
     )]
-    pub platform_treasury: UncheckedAccount<'info>,
+    pub event_vault: Account<'info, EventVault>,
 
     #[account(
         mut,
@@ -58,10 +64,133 @@ pub struct PurchaseTickets<'info> {
     )]
     pub reentrancy_guard: Account<'info, ReentrancyGuard>,
 
+    #[account(
+        mut,
+        seeds = [
+            TICKET_TIER_SEED,
+            event.key().as_ref(),
+            &[args.tier_index]
+        ],
+        bump = tier.bump,
+    )]
+    pub tier: Account<'info, TicketTier>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEAT_MAP_SEED,
+            event.key().as_ref(),
+            args.section.as_bytes()
+        ],
+        bump = seat_map.bump,
+    )]
+    pub seat_map: Account<'info, SeatMap>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_TREE_SEED,
+            event.key().as_ref(),
+            &[event.active_tree]
+        ],
+        bump = event_tree.bump,
+    )]
+    pub event_tree: Account<'info, EventTree>,
+
+    /// CHECK: Bubblegum tree config, validated by Bubblegum during the mint
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Active concurrent merkle tree, validated by account compression
+    #[account(
+        mut,
+        address = event_tree.merkle_tree @ TicketTokenError::InvalidMerkleTree,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Next tree in line, only needed when this order fills the active tree
+    #[account(
+        mut,
+        seeds = [
+            EVENT_TREE_SEED,
+            event.key().as_ref(),
+            &[event.active_tree.saturating_add(1)]
+        ],
+        bump = next_event_tree.bump,
+    )]
+    pub next_event_tree: Option<Account<'info, EventTree>>,
+
+    /// CHECK: Bubblegum tree config for the next tree, validated by Bubblegum
+    #[account(mut)]
+    pub next_tree_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked against next_event_tree in the handler
+    #[account(mut)]
+    pub next_merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Program PDA that holds tree authority for this event's tree
+    #[account(
+        seeds = [TREE_CREATOR_SEED, event.key().as_ref()],
+        bump,
+    )]
+    pub tree_creator: UncheckedAccount<'info>,
+
+    /// CHECK: Program PDA that is the update authority of the event collection
+    #[account(
+        seeds = [COLLECTION_AUTHORITY_SEED, event.key().as_ref()],
+        bump,
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Event collection mint
+    #[account(
+        address = event.collection_mint @ TicketTokenError::InvalidCollection,
+    )]
+    pub collection_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Collection metadata, validated by Bubblegum (size is bumped per mint)
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition, validated by Bubblegum
+    pub collection_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum PDA that signs collection updates
+    #[account(
+        seeds = [b"collection_cpi"],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program used by Bubblegum for leaf logs
+    #[account(address = spl_noop::ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = spl_account_compression::ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn purchase_tickets(ctx: Context<PurchaseTickets>, args: MintTicketArgs) -> Result<()> {
+/// The buyer may pass the order's Ticket PDAs as remaining accounts, in
+/// ticket number order, to register them in the same transaction as the
+/// sale. Orders too large to fit them are registered with register_ticket.
+pub fn purchase_tickets<'info>(
+    ctx: Context<'_, '_, '_, 'info, PurchaseTickets<'info>>,
+    args: MintTicketArgs,
+) -> Result<()> {
     // Lock reentrancy guard
     ctx.accounts.reentrancy_guard.lock()?;
 
@@ -79,6 +208,12 @@ pub fn purchase_tickets(ctx: Context<PurchaseTickets>, args: MintTicketArgs) ->
         args.quantity > 0 && args.quantity <= MAX_TICKET_PURCHASE,
         TicketTokenError::InvalidQuantity
     );
+    let ticket_accounts = ctx.remaining_accounts;
+    require!(
+        (ticket_accounts.is_empty() || ticket_accounts.len() == args.quantity as usize)
+            && args.owner_commitments.len() == ticket_accounts.len(),
+        TicketTokenError::InvalidTicketAccounts
+    );
 
     // Check capacity
     let new_sold = safe_add(event.tickets_sold as u64, args.quantity as u64)?;
@@ -87,83 +222,229 @@ pub fn purchase_tickets(ctx: Context<PurchaseTickets>, args: MintTicketArgs) ->
         TicketTokenError::InsufficientTickets
     );
 
+    // Check the tier's sale window and its own capacity
+    let tier = &ctx.accounts.tier;
+    tier.check_sale_window(current_time)?;
+    require!(
+        args.quantity as u32 <= tier.remaining(),
+        TicketTokenError::TierSoldOut
+    );
+
+    // Tiers only sell the sections they were priced for
+    require!(
+        ctx.accounts.seat_map.allows_tier(tier.index),
+        TicketTokenError::SectionNotInTier
+    );
+
+    // Claim the seats up front; any conflict aborts the whole purchase
+    ctx.accounts.seat_map.claim(args.row, args.seat_start, args.quantity as u32)?;
+
     // Calculate total cost
-    let ticket_cost = safe_mul(event.ticket_price, args.quantity as u64)?;
+    let ticket_cost = safe_mul(tier.price, args.quantity as u64)?;
 
-    // Calculate platform fee
-    let platform_fee = calculate_fee(ticket_cost, ctx.accounts.platform.fee_bps)?;
-    let venue_amount = ticket_cost.checked_sub(platform_fee)
-        .ok_or(TicketTokenError::MathOverflow)?;
+    // Calculate platform fee (taken out of the ticket price at settlement)
+    let platform_fee = calculate_fee(ticket_cost, event.platform_fee_bps)?;
 
-    // Transfer to venue
-    let venue_transfer = anchor_lang::system_program::Transfer {
+    // Escrow the full amount in the event vault
+    let vault_transfer = anchor_lang::system_program::Transfer {
         from: ctx.accounts.buyer.to_account_info(),
-        to: ctx.accounts.venue_treasury.to_account_info(),
+        to: ctx.accounts.event_vault.to_account_info(),
     };
     anchor_lang::system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
-            venue_transfer,
+            vault_transfer,
         ),
-        venue_amount,
+        ticket_cost,
     )?;
-
-    // Transfer platform fee
-    if platform_fee > 0 {
-        let fee_transfer = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.buyer.to_account_info(),
-            to: ctx.accounts.platform_treasury.to_account_info(),
-        };
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                fee_transfer,
-            ),
-            platform_fee,
-        )?;
-    }
+    ctx.accounts.event_vault.record_sale(ticket_cost, platform_fee)?;
+    ctx.accounts.platform.record_sale(args.quantity as u64, platform_fee)?;
 
     // Store values before mutable borrows
     let event_key = ctx.accounts.event.key();
-    let price_each = event.ticket_price;
+    let price_each = ctx.accounts.tier.price;
     let venue_key = ctx.accounts.venue.key();
-    let platform_treasury_key = ctx.accounts.platform_treasury.key();
+    let royalties = calculate_royalties(
+        event.artist_wallet,
+        event.artist_percentage,
+        &ctx.accounts.venue.payout_wallets(),
+        event.venue_percentage,
+        ctx.accounts.platform.treasury,
+        event.platform_fee_bps,
+    )?;
 
     // Update event stats
+    // Ticket numbers come from a separate counter because refunds lower
+    // tickets_sold and numbers must never be handed out twice
     let event = &mut ctx.accounts.event;
     event.tickets_sold = new_sold as u32;
+    let start_ticket_number = event.next_ticket_number;
+    event.next_ticket_number = start_ticket_number
+        .checked_add(args.quantity as u32)
+        .ok_or(TicketTokenError::MathOverflow)?;
+
+    // Update tier stats
+    let tier = &mut ctx.accounts.tier;
+    tier.sold = tier.sold.checked_add(args.quantity as u32).ok_or(TicketTokenError::MathOverflow)?;
 
     // Update venue stats
     let venue = &mut ctx.accounts.venue;
     venue.total_sales = safe_add(venue.total_sales, args.quantity as u64)?;
 
-    // In a real implementation, we would mint compressed NFTs here
-    let start_ticket_number = event.tickets_sold - args.quantity as u32;
-    for i in 0..args.quantity {
-        let ticket_number = start_ticket_number.checked_add(i as u32).ok_or(TicketTokenError::MathOverflow)?;
+    // Mint one compressed NFT per ticket, filling the active tree first
+    // and spilling the rest of the order onto the next tree
+    let quantity = args.quantity as u32;
+    let active_count = quantity.min(ctx.accounts.event_tree.remaining());
+    let spill_count = quantity - active_count;
+
+    let tree_creator_bump = [ctx.bumps.tree_creator];
+    let collection_authority_bump = [ctx.bumps.collection_authority];
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[TREE_CREATOR_SEED, event_key.as_ref(), &tree_creator_bump],
+        &[COLLECTION_AUTHORITY_SEED, event_key.as_ref(), &collection_authority_bump],
+    ];
+
+    let bubblegum_program = ctx.accounts.bubblegum_program.to_account_info();
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let tree_creator = ctx.accounts.tree_creator.to_account_info();
+    let collection_authority = ctx.accounts.collection_authority.to_account_info();
+    let collection_mint = ctx.accounts.collection_mint.to_account_info();
+    let collection_metadata = ctx.accounts.collection_metadata.to_account_info();
+    let collection_edition = ctx.accounts.collection_edition.to_account_info();
+    let bubblegum_signer = ctx.accounts.bubblegum_signer.to_account_info();
+    let token_metadata_program = ctx.accounts.token_metadata_program.to_account_info();
+    let log_wrapper = ctx.accounts.log_wrapper.to_account_info();
+    let compression_program = ctx.accounts.compression_program.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let tree_config = ctx.accounts.tree_config.to_account_info();
+    let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+
+    let active_tree = TicketMintAccounts {
+        bubblegum_program: &bubblegum_program,
+        tree_config: &tree_config,
+        merkle_tree: &merkle_tree,
+        leaf_owner: &buyer,
+        payer: &buyer,
+        tree_creator: &tree_creator,
+        collection_authority: &collection_authority,
+        collection_mint: &collection_mint,
+        collection_metadata: &collection_metadata,
+        collection_edition: &collection_edition,
+        bubblegum_signer: &bubblegum_signer,
+        token_metadata_program: &token_metadata_program,
+        log_wrapper: &log_wrapper,
+        compression_program: &compression_program,
+        system_program: &system_program,
+    };
+
+    let next_tree_infos = match (
+        &ctx.accounts.next_event_tree,
+        &ctx.accounts.next_tree_config,
+        &ctx.accounts.next_merkle_tree,
+    ) {
+        (Some(next_event_tree), Some(next_tree_config), Some(next_merkle_tree)) => {
+            require_keys_eq!(
+                next_merkle_tree.key(),
+                next_event_tree.merkle_tree,
+                TicketTokenError::InvalidMerkleTree
+            );
+            require!(
+                next_event_tree.remaining() >= spill_count,
+                TicketTokenError::TreeFull
+            );
+            Some((next_tree_config.to_account_info(), next_merkle_tree.to_account_info()))
+        }
+        _ => None,
+    };
+    require!(
+        spill_count == 0 || next_tree_infos.is_some(),
+        TicketTokenError::TreeFull
+    );
+    // Leaf nonces (and so asset IDs) continue from each tree's mint count
+    let active_nonce = ctx.accounts.event_tree.minted as u64;
+    let next_nonce = ctx.accounts.next_event_tree.as_ref().map_or(0, |tree| tree.minted as u64);
+
+    let next_tree = next_tree_infos.as_ref().map(|(next_tree_config, next_merkle_tree)| TicketMintAccounts {
+        tree_config: next_tree_config,
+        merkle_tree: next_merkle_tree,
+        ..active_tree
+    });
+
+    for i in 0..quantity {
+        let ticket_number = start_ticket_number.checked_add(i).ok_or(TicketTokenError::MathOverflow)?;
         let metadata = create_ticket_metadata(
             event,
+            tier,
             ticket_number,
             &args.section,
-            &args.row,
-            &format!("{}", args.seat_start.checked_add(i as u32).ok_or(TicketTokenError::MathOverflow)?),
-            platform_treasury_key,
+            args.row,
+            args.seat_start + i,
+            &royalties,
         );
 
-        msg!("Would mint ticket #{} with metadata: {}", ticket_number, metadata.name);
-        msg!("Creators: Venue ({}): 50%, Platform ({}): 50%", venue_key, platform_treasury_key);
-        msg!("Royalty: 10% (1000 basis points)");
+        let (tree_accounts, nonce) = match &next_tree {
+            Some(next_tree) if i >= active_count => (next_tree, next_nonce + (i - active_count) as u64),
+            _ => (&active_tree, active_nonce + i as u64),
+        };
+        mint_ticket(tree_accounts, metadata, signer_seeds)?;
+
+        if let Some(ticket_info) = ticket_accounts.get(i as usize) {
+            let ticket = Ticket::new(
+                event_key,
+                ticket_number as u64,
+                args.tier_index,
+                get_asset_id(tree_accounts.merkle_tree.key, nonce),
+                args.owner_commitments[i as usize],
+                buyer.key(),
+                buyer.key(),
+            );
+            create_ticket_account(ticket_info, &buyer, &system_program, ticket)?;
+        }
+
+        msg!("Minted ticket #{} into {}", ticket_number, tree_accounts.merkle_tree.key());
+    }
+
+    // Track leaves per tree and roll over once the active tree is full.
+    // A tree's first sale fixes which ticket number its leaf 0 holds.
+    let event_tree = &mut ctx.accounts.event_tree;
+    if event_tree.minted == 0 {
+        event_tree.first_ticket = start_ticket_number;
+    }
+    event_tree.minted = event_tree.minted.checked_add(active_count).ok_or(TicketTokenError::MathOverflow)?;
+    if let Some(next_event_tree) = ctx.accounts.next_event_tree.as_mut() {
+        if next_event_tree.minted == 0 && spill_count > 0 {
+            next_event_tree.first_ticket = start_ticket_number
+                .checked_add(active_count)
+                .ok_or(TicketTokenError::MathOverflow)?;
+        }
+        next_event_tree.minted = next_event_tree.minted.checked_add(spill_count).ok_or(TicketTokenError::MathOverflow)?;
+        if event_tree.remaining() == 0 {
+            event.active_tree = next_event_tree.index;
+            event.merkle_tree = next_event_tree.merkle_tree;
+            msg!("Event rolled over to tree #{}", next_event_tree.index);
+        }
     }
 
     emit!(TicketsPurchased {
         buyer: ctx.accounts.buyer.key(),
         event: event_key,
         venue: venue_key,
+        tier_index: args.tier_index,
         quantity: args.quantity,
         price_each,
         total_paid: ticket_cost,
         platform_fee,
         start_ticket_number,
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        merkle_tree_quantity: active_count as u8,
+        spill_merkle_tree: ctx.accounts.next_merkle_tree
+            .as_ref()
+            .filter(|_| spill_count > 0)
+            .map(|tree| tree.key()),
+        spill_quantity: spill_count as u8,
+        section: args.section.clone(),
+        row: args.row,
+        seat_start: args.seat_start,
         timestamp: current_time,
     });
 
@@ -175,16 +456,99 @@ pub fn purchase_tickets(ctx: Context<PurchaseTickets>, args: MintTicketArgs) ->
     Ok(())
 }
 
+/// Create the Ticket PDA for a freshly minted ticket and write `ticket`
+/// into it with the PDA's bump
+fn create_ticket_account<'info>(
+    ticket_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    ticket: Ticket,
+) -> Result<()> {
+    let event = ticket.event;
+    let ticket_id_bytes = ticket.ticket_id.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"ticket", event.as_ref(), &ticket_id_bytes],
+        &crate::ID,
+    );
+    require_keys_eq!(ticket_info.key(), expected, TicketTokenError::InvalidTicket);
+
+    let space = 8 + Ticket::SIZE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer_seeds: &[&[&[u8]]] = &[&[b"ticket", event.as_ref(), &ticket_id_bytes, &[bump]]];
+    let current_lamports = ticket_info.lamports();
+    if current_lamports == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: ticket_info.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        // Someone already sent lamports to the PDA, which would make
+        // create_account fail; top it up and take it over instead
+        let shortfall = rent.saturating_sub(current_lamports);
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: ticket_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate { account_to_allocate: ticket_info.clone() },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Assign { account_to_assign: ticket_info.clone() },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    let ticket = Ticket { bump, ..ticket };
+    ticket.try_serialize(&mut &mut ticket_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
 #[event]
 pub struct TicketsPurchased {
     pub buyer: Pubkey,
     pub event: Pubkey,
     pub venue: Pubkey,
+    pub tier_index: u8,
     pub quantity: u8,
     pub price_each: u64,
     pub total_paid: u64,
     pub platform_fee: u64,
     pub start_ticket_number: u32,
+    /// Tree the order started minting into, and how many tickets landed there
+    pub merkle_tree: Pubkey,
+    pub merkle_tree_quantity: u8,
+    /// Next tree the rest of the order spilled onto, if the first one filled
+    pub spill_merkle_tree: Option<Pubkey>,
+    pub spill_quantity: u8,
+    pub section: String,
+    pub row: u16,
+    pub seat_start: u32,
     pub timestamp: i64,
 }
 
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, RoleRegistry, AdminOperation, AdminOperationKind};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct QueueAdminOperation<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AdminOperation::SIZE,
+        seeds = [ADMIN_OPERATION_SEED, platform.next_operation_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub admin_operation: Account<'info, AdminOperation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Propose a platform parameter change. It can be executed by anyone once
+/// `platform.timelock_delay` has passed, which gives venues notice of fee moves.
+pub fn queue_admin_operation(ctx: Context<QueueAdminOperation>, kind: AdminOperationKind) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let platform = &mut ctx.accounts.platform;
+    let allowed = match kind.required_role() {
+        Some(role) => ctx.accounts.role_registry.authorizes(&platform.owner, &authority, role),
+        None => authority == platform.owner,
+    };
+    require!(allowed, TicketTokenError::Unauthorized);
+    kind.validate()?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let executable_at = current_time
+        .checked_add(platform.timelock_delay)
+        .ok_or(TicketTokenError::MathOverflow)?;
+
+    let operation = &mut ctx.accounts.admin_operation;
+    operation.id = platform.next_operation_id;
+    operation.kind = kind;
+    operation.proposer = authority;
+    operation.proposed_at = current_time;
+    operation.executable_at = executable_at;
+    operation.executed = false;
+    operation.cancelled = false;
+    operation.bump = ctx.bumps.admin_operation;
+
+    platform.next_operation_id = platform.next_operation_id
+        .checked_add(1)
+        .ok_or(TicketTokenError::MathOverflow)?;
+
+    emit!(AdminOperationQueued {
+        id: operation.id,
+        kind,
+        proposer: authority,
+        executable_at,
+        timestamp: current_time,
+    });
+
+    msg!("Admin operation {} queued, executable at {}", operation.id, executable_at);
+
+    Ok(())
+}
+
+#[event]
+pub struct AdminOperationQueued {
+    pub id: u64,
+    pub kind: AdminOperationKind,
+    pub proposer: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
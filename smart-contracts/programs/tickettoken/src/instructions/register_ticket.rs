@@ -1,50 +1,140 @@
 use anchor_lang::prelude::*;
-use crate::state::{Event, Ticket};
+use mpl_bubblegum::utils::get_asset_id;
+use crate::state::{Platform, Venue, Event, EventTree, Ticket, TicketLeafArgs, TicketLeafMetadata, Role, RoleRegistry, VenueStaff};
 use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::merkle::{ticket_leaf_metadata, verify_ticket_leaf};
 
 #[derive(Accounts)]
-#[instruction(ticket_id: u64, nft_asset_id: Pubkey, owner_id: String)]
+#[instruction(leaf: TicketLeafArgs)]
 pub struct RegisterTicket<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        // Ticket numbers come from next_ticket_number, which unlike
+        // tickets_sold never drops on refunds
+        constraint = leaf.ticket_number < event.next_ticket_number @ TicketTokenError::TicketNotSold,
+    )]
     pub event: Account<'info, Event>,
     
+    pub venue: Account<'info, Venue>,
+    
+    /// Staff record, required unless the signer is the venue owner or a platform minter
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+    
+    /// Tree the ticket was minted into
+    #[account(
+        seeds = [EVENT_TREE_SEED, event.key().as_ref(), &[leaf.tree_index]],
+        bump = event_tree.bump,
+    )]
+    pub event_tree: Account<'info, EventTree>,
+    
     #[account(
         init,
         payer = authority,
-        seeds = [b"ticket", event.key().as_ref(), ticket_id.to_le_bytes().as_ref()],
+        seeds = [b"ticket", event.key().as_ref(), (leaf.ticket_number as u64).to_le_bytes().as_ref()],
         bump,
         space = 8 + Ticket::SIZE,
     )]
     pub ticket: Account<'info, Ticket>,
     
+    /// CHECK: Wallet holding the ticket leaf, checked against the leaf.
+    /// Becomes the ticket's owner_wallet, custodial or not.
+    pub leaf_owner: UncheckedAccount<'info>,
+    
+    /// CHECK: Tree holding the ticket leaf
+    #[account(address = event_tree.merkle_tree @ TicketTokenError::InvalidMerkleTree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account compression program
+    #[account(address = spl_account_compression::ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
-pub fn register_ticket(
-    ctx: Context<RegisterTicket>,
-    ticket_id: u64,
-    nft_asset_id: Pubkey,
-    owner_id: String,
+/// Backfills the registry for sold tickets whose PDA was not created by
+/// purchase_tickets. Only the platform's minters and box office staff
+/// may register, and only for a leaf they prove is in the event's tree;
+/// the tier is read from that leaf. Proof nodes go in remaining accounts.
+pub fn register_ticket<'info>(
+    ctx: Context<'_, '_, '_, 'info, RegisterTicket<'info>>,
+    leaf: TicketLeafArgs,
+    metadata: TicketLeafMetadata,
+    owner_commitment: [u8; 32],
 ) -> Result<()> {
-    require!(owner_id.len() <= Ticket::MAX_OWNER_ID_LEN, TicketTokenError::OwnerIdTooLong);
-    
-    let ticket = &mut ctx.accounts.ticket;
-    ticket.event = ctx.accounts.event.key();
-    ticket.ticket_id = ticket_id;
-    ticket.nft_asset_id = nft_asset_id;
-    ticket.current_owner_id = owner_id.clone();
-    ticket.used = false;
-    ticket.verified_at = None;
-    ticket.transfer_count = 0;
-    ticket.bump = ctx.bumps.ticket;
-    
-    msg!("Ticket {} registered for event {} with owner {}", 
-        ticket_id, 
-        ctx.accounts.event.key(), 
-        owner_id
+    let authority = ctx.accounts.authority.key();
+    let venue = &ctx.accounts.venue;
+    require!(
+        ctx.accounts.role_registry.authorizes(&ctx.accounts.platform.owner, &authority, Role::TicketMinter)
+            || VenueStaff::is_authorized(
+                &venue.key(),
+                &venue.owner,
+                &authority,
+                ctx.accounts.venue_staff.as_deref(),
+                VenueStaff::ISSUE_COMPS,
+                Clock::get()?.unix_timestamp,
+            ),
+        TicketTokenError::Unauthorized
     );
     
+    // The asset ID follows from the tree and leaf, so it can't be made up,
+    // and a ticket burned for a refund can't be brought back
+    let ticket_id = leaf.ticket_number as u64;
+    let event_tree = &ctx.accounts.event_tree;
+    require!(
+        event_tree.nonce_of(ticket_id) == Some(leaf.nonce),
+        TicketTokenError::TicketNotSold
+    );
+    require!(!event_tree.is_refunded(leaf.nonce), TicketTokenError::TicketRefunded);
+    let nft_asset_id = get_asset_id(&event_tree.merkle_tree, leaf.nonce);
+    
+    // The leaf's URI carries the tier it was bought in, and its owner is
+    // the wallet that can refund it, so the registry records both
+    let owner_wallet = ctx.accounts.leaf_owner.key();
+    let metadata = ticket_leaf_metadata(&ctx.accounts.event, &metadata, &leaf)?;
+    verify_ticket_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &owner_wallet,
+        &metadata,
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+    
+    ctx.accounts.ticket.set_inner(Ticket {
+        bump: ctx.bumps.ticket,
+        ..Ticket::new(
+            ctx.accounts.event.key(),
+            ticket_id,
+            leaf.tier_index,
+            nft_asset_id,
+            owner_commitment,
+            owner_wallet,
+            authority,
+        )
+    });
+    
+    msg!("Ticket {} registered for event {}", ticket_id, ctx.accounts.event.key());
+    
     Ok(())
 }
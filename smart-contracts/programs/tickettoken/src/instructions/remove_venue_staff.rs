@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::{Venue, VenueStaff};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveVenueStaff<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        constraint = venue.owner == owner.key() @ TicketTokenError::UnauthorizedVenue,
+    )]
+    pub venue: Account<'info, Venue>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), venue_staff.staff.as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Account<'info, VenueStaff>,
+}
+
+/// Revoke a staff key. Allowed while paused so a leaked key can be cut off.
+pub fn remove_venue_staff(ctx: Context<RemoveVenueStaff>) -> Result<()> {
+    let venue_staff = &ctx.accounts.venue_staff;
+    
+    emit!(VenueStaffRemoved {
+        venue: venue_staff.venue,
+        staff: venue_staff.staff,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Staff {} removed from venue {}", venue_staff.staff, venue_staff.venue);
+    
+    Ok(())
+}
+
+#[event]
+pub struct VenueStaffRemoved {
+    pub venue: Pubkey,
+    pub staff: Pubkey,
+    pub timestamp: i64,
+}
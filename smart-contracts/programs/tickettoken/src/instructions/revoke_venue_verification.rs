@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, Role, RoleRegistry};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RevokeVenueVerification<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+        constraint = role_registry.authorizes(&platform.owner, &authority.key(), Role::VenueVerifier)
+            || role_registry.authorizes(&platform.owner, &authority.key(), Role::ComplianceOfficer)
+            @ TicketTokenError::Unauthorized,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        constraint = venue.verified @ TicketTokenError::VenueNotVerified
+    )]
+    pub venue: Account<'info, Venue>,
+}
+
+/// Stops new events and sales at the venue. Tickets already sold stay
+/// valid for entry, transfer and refund. verify_venue can restore it.
+pub fn revoke_venue_verification(ctx: Context<RevokeVenueVerification>, reason: u16) -> Result<()> {
+    require!(reason != 0, TicketTokenError::InvalidRevocationReason);
+    
+    let venue = &mut ctx.accounts.venue;
+    venue.verified = false;
+    venue.revocation_reason = reason;
+    
+    emit!(VenueVerificationRevoked {
+        venue: venue.key(),
+        revoked_by: ctx.accounts.authority.key(),
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Venue {} verification revoked (reason {})", venue.key(), reason);
+    
+    Ok(())
+}
+
+#[event]
+pub struct VenueVerificationRevoked {
+    pub venue: Pubkey,
+    pub revoked_by: Pubkey,
+    pub reason: u16,
+    pub timestamp: i64,
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, Event, VenueStaff};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::validation::validate_checkin_window;
+
+#[derive(Accounts)]
+pub struct SetCheckinWindow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub venue: Account<'info, Venue>,
+
+    /// Staff record, required when the signer is not the venue owner
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_SEED,
+            venue.key().as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        constraint = !event.cancelled @ TicketTokenError::EventCancelled,
+    )]
+    pub event: Account<'info, Event>,
+}
+
+pub fn set_checkin_window(
+    ctx: Context<SetCheckinWindow>,
+    doors_open: i64,
+    checkin_close: i64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    VenueStaff::authorize(
+        &ctx.accounts.venue,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.venue_staff.as_deref(),
+        VenueStaff::EDIT_EVENTS,
+        current_time,
+    )?;
+
+    validate_checkin_window(doors_open, checkin_close)?;
+
+    let event = &mut ctx.accounts.event;
+    event.doors_open = doors_open;
+    event.checkin_close = checkin_close;
+
+    emit!(CheckinWindowUpdated {
+        event: event.key(),
+        doors_open,
+        checkin_close,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+
+    msg!("Check-in opens {}s before start and closes {}s after end", doors_open, checkin_close);
+
+    Ok(())
+}
+
+#[event]
+pub struct CheckinWindowUpdated {
+    pub event: Pubkey,
+    pub doors_open: i64,
+    pub checkin_close: i64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Event, Role, RoleRegistry};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetDisputeHold<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+        constraint = role_registry.authorizes(&platform.owner, &authority.key(), Role::ComplianceOfficer) @ TicketTokenError::Unauthorized,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_SEED,
+            event.venue.as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = !event.settled @ TicketTokenError::EventAlreadySettled,
+    )]
+    pub event: Account<'info, Event>,
+}
+
+pub fn set_dispute_hold(ctx: Context<SetDisputeHold>, on_hold: bool) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    event.dispute_hold = on_hold;
+
+    emit!(DisputeHoldUpdated {
+        event: event.key(),
+        on_hold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Settlement hold for event {}: {}", event.key(), on_hold);
+
+    Ok(())
+}
+
+#[event]
+pub struct DisputeHoldUpdated {
+    pub event: Pubkey,
+    pub on_hold: bool,
+    pub timestamp: i64,
+}
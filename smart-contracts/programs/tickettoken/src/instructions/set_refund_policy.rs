@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Role, RoleRegistry};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetRefundPolicy<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+        constraint = role_registry.authorizes(&platform.owner, &authority.key(), Role::FeeManager) @ TicketTokenError::Unauthorized,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+}
+
+pub fn set_refund_policy(ctx: Context<SetRefundPolicy>, refund_fee_on_cancel: bool) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+    platform.refund_fee_on_cancel = refund_fee_on_cancel;
+
+    emit!(RefundPolicyUpdated {
+        refund_fee_on_cancel,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Platform fee refunded on cancellation: {}", refund_fee_on_cancel);
+
+    Ok(())
+}
+
+#[event]
+pub struct RefundPolicyUpdated {
+    pub refund_fee_on_cancel: bool,
+    pub timestamp: i64,
+}
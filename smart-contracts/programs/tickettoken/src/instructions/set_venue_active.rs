@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, Role, RoleRegistry};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetVenueActive<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(mut)]
+    pub venue: Account<'info, Venue>,
+}
+
+pub fn set_venue_active(ctx: Context<SetVenueActive>, active: bool) -> Result<()> {
+    // The venue can close itself; platform admins can suspend it
+    let authority = ctx.accounts.authority.key();
+    let owner = &ctx.accounts.platform.owner;
+    let registry = &ctx.accounts.role_registry;
+    let is_admin = registry.authorizes(owner, &authority, Role::VenueVerifier)
+        || registry.authorizes(owner, &authority, Role::ComplianceOfficer);
+    require!(
+        authority == ctx.accounts.venue.owner || is_admin,
+        TicketTokenError::Unauthorized
+    );
+    // An admin suspension stays until an admin lifts it
+    require!(
+        is_admin || !ctx.accounts.venue.is_suspended(),
+        TicketTokenError::VenueSuspended
+    );
+    
+    let venue = &mut ctx.accounts.venue;
+    venue.active = active;
+    venue.deactivated_by = if active { Pubkey::default() } else { authority };
+    
+    emit!(VenueActiveChanged {
+        venue: venue.key(),
+        active,
+        changed_by: authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Venue {} active: {}", venue.key(), active);
+    
+    Ok(())
+}
+
+#[event]
+pub struct VenueActiveChanged {
+    pub venue: Pubkey,
+    pub active: bool,
+    pub changed_by: Pubkey,
+    pub timestamp: i64,
+}
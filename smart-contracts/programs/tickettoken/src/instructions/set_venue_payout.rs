@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, PayoutWallet};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetVenuePayout<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        constraint = venue.owner == owner.key() @ TicketTokenError::UnauthorizedVenue,
+    )]
+    pub venue: Account<'info, Venue>,
+}
+
+/// Route venue proceeds to up to MAX_PAYOUT_WALLETS wallets, e.g. the
+/// venue, a promoter and a landlord. Weights are basis points summing to
+/// 10,000. Applies to settlements and to royalties on tickets minted later.
+pub fn set_venue_payout(ctx: Context<SetVenuePayout>, wallets: Vec<PayoutWallet>) -> Result<()> {
+    Venue::validate_payout_wallets(&wallets)?;
+    
+    let venue = &mut ctx.accounts.venue;
+    venue.payout_count = wallets.len() as u8;
+    venue.payout_wallets = [PayoutWallet::default(); MAX_PAYOUT_WALLETS];
+    venue.payout_wallets[..wallets.len()].copy_from_slice(&wallets);
+    
+    emit!(VenuePayoutUpdated {
+        venue: venue.key(),
+        wallets,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Venue {} payout set to {} wallet(s)", venue.key(), venue.payout_count);
+    
+    Ok(())
+}
+
+#[event]
+pub struct VenuePayoutUpdated {
+    pub venue: Pubkey,
+    pub wallets: Vec<PayoutWallet>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue, Event, EventVault, Settlement};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SettleEvent<'info> {
+    /// Anyone can settle once the event is over; they pay for the record
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    pub venue: Account<'info, Venue>,
+
+    #[account(
+        mut,
+        seeds = [
+            EVENT_SEED,
+            venue.key().as_ref(),
+            event.event_id.to_le_bytes().as_ref()
+        ],
+        bump = event.bump,
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+        constraint = !event.cancelled @ TicketTokenError::EventCancelled,
+        constraint = !event.settled @ TicketTokenError::EventAlreadySettled,
+        constraint = !event.dispute_hold @ TicketTokenError::SettlementOnHold,
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [EVENT_VAULT_SEED, event.key().as_ref()],
+        bump = event_vault.bump,
+    )]
+    pub event_vault: Account<'info, EventVault>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Settlement::SIZE,
+        seeds = [SETTLEMENT_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub settlement: Account<'info, Settlement>,
+
+    /// CHECK: Platform treasury, receives the platform fees
+    #[account(
+        mut,
+        address = platform.treasury @ TicketTokenError::Unauthorized,
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Artist wallet, required when the artist has a share
+    #[account(
+        mut,
+        address = event.artist_wallet @ TicketTokenError::InvalidArtistWallet,
+    )]
+    pub artist_wallet: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: the venue's payout wallets, writable, in order
+}
+
+pub fn settle_event(ctx: Context<SettleEvent>) -> Result<()> {
+    let event = &ctx.accounts.event;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Wait for the show to finish and for the refund window to lapse,
+    // so no refund can race the payout
+    let refunds_close = event.start_time
+        .checked_add(event.refund_window)
+        .ok_or(TicketTokenError::MathOverflow)?;
+    require!(
+        current_time >= event.end_time.max(refunds_close),
+        TicketTokenError::EventNotEnded
+    );
+
+    let vault = &mut ctx.accounts.event_vault;
+    let split = Settlement::split(vault.balance(), vault.fees_held(), event.artist_percentage)?;
+    let released = vault.record_settlement()?;
+    require!(split.total()? == released, TicketTokenError::MathOverflow);
+
+    let vault_info = ctx.accounts.event_vault.to_account_info();
+    EventVault::pay_out(&vault_info, &ctx.accounts.treasury.to_account_info(), split.platform)?;
+    if split.artist > 0 {
+        let artist_wallet = ctx.accounts.artist_wallet
+            .as_ref()
+            .ok_or(TicketTokenError::InvalidArtistWallet)?;
+        EventVault::pay_out(&vault_info, &artist_wallet.to_account_info(), split.artist)?;
+    }
+
+    // The venue share follows the venue's weighted payout wallets
+    let venue_shares = ctx.accounts.venue.split_payout(split.venue)?;
+    require!(
+        ctx.remaining_accounts.len() == venue_shares.len(),
+        TicketTokenError::PayoutWalletMismatch
+    );
+    for ((wallet, share), account) in venue_shares.iter().zip(ctx.remaining_accounts) {
+        require!(
+            account.key() == *wallet && account.is_writable,
+            TicketTokenError::PayoutWalletMismatch
+        );
+        EventVault::pay_out(&vault_info, account, *share)?;
+    }
+
+    let vault = &ctx.accounts.event_vault;
+    let settlement = &mut ctx.accounts.settlement;
+    settlement.event = ctx.accounts.event.key();
+    settlement.gross_sales = vault.gross_sales;
+    settlement.refunds = vault.refunded;
+    settlement.platform_payout = split.platform;
+    settlement.artist_payout = split.artist;
+    settlement.venue_payout = split.venue;
+    settlement.tickets_sold = ctx.accounts.event.tickets_sold;
+    settlement.settled_by = ctx.accounts.authority.key();
+    settlement.settled_at = current_time;
+    settlement.bump = ctx.bumps.settlement;
+
+    let event = &mut ctx.accounts.event;
+    event.settled = true;
+
+    emit!(EventSettled {
+        event: event.key(),
+        venue: event.venue,
+        settled_by: settlement.settled_by,
+        gross_sales: settlement.gross_sales,
+        refunds: settlement.refunds,
+        platform_payout: split.platform,
+        artist_payout: split.artist,
+        venue_payout: split.venue,
+        timestamp: current_time,
+    });
+
+    msg!(
+        "Event {} settled: venue {} / artist {} / platform {} lamports",
+        event.key(),
+        split.venue,
+        split.artist,
+        split.platform
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct EventSettled {
+    pub event: Pubkey,
+    pub venue: Pubkey,
+    pub settled_by: Pubkey,
+    pub gross_sales: u64,
+    pub refunds: u64,
+    pub platform_payout: u64,
+    pub artist_payout: u64,
+    pub venue_payout: u64,
+    pub timestamp: i64,
+}
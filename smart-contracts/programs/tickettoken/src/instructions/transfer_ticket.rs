@@ -1,42 +1,219 @@
 use anchor_lang::prelude::*;
-use crate::state::{Event, Ticket};
+use mpl_bubblegum::utils::get_asset_id;
+use crate::state::{Platform, Venue, Event, EventTree, Ticket, TicketLeafArgs, TicketLeafMetadata, Role, RoleRegistry, VenueStaff};
 use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::merkle::{ticket_leaf_metadata, transfer_ticket_leaf, TicketTransferAccounts};
 
 #[derive(Accounts)]
+#[instruction(leaf: TicketLeafArgs)]
 pub struct TransferTicket<'info> {
     pub authority: Signer<'info>,
     
-    #[account(constraint = event.resaleable @ TicketTokenError::TransferNotAllowed)]
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+    )]
     pub event: Account<'info, Event>,
     
+    pub venue: Account<'info, Venue>,
+    
+    /// Staff record, lets box office keys move custodial tickets
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), authority.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+    
     #[account(
         mut,
         seeds = [b"ticket", event.key().as_ref(), ticket.ticket_id.to_le_bytes().as_ref()],
         bump = ticket.bump,
+        constraint = ticket.ticket_id == leaf.ticket_number as u64 @ TicketTokenError::InvalidTicket,
     )]
     pub ticket: Account<'info, Ticket>,
+    
+    /// Wallet holding the leaf; the backend's wallet for custodial tickets
+    #[account(
+        constraint = leaf_owner.key() == ticket.owner_wallet @ TicketTokenError::NotTicketHolder,
+    )]
+    pub leaf_owner: Signer<'info>,
+    
+    /// CHECK: Wallet receiving the leaf, recorded as the new owner_wallet
+    pub new_leaf_owner: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [EVENT_TREE_SEED, event.key().as_ref(), &[leaf.tree_index]],
+        bump = event_tree.bump,
+    )]
+    pub event_tree: Account<'info, EventTree>,
+    
+    /// CHECK: Bubblegum tree config, validated by Bubblegum during the transfer
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Tree holding the ticket leaf
+    #[account(
+        mut,
+        address = event_tree.merkle_tree @ TicketTokenError::InvalidMerkleTree,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program used by Bubblegum for leaf logs
+    #[account(address = spl_noop::ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account compression program
+    #[account(address = spl_account_compression::ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
-pub fn transfer_ticket(
-    ctx: Context<TransferTicket>,
-    new_owner_id: String,
+/// Moves a ticket to a new holder. The cNFT leaf moves with the registry
+/// entry, so whoever holds the ticket afterwards can refund it. Proof
+/// nodes go in remaining accounts.
+pub fn transfer_ticket<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferTicket<'info>>,
+    leaf: TicketLeafArgs,
+    metadata: TicketLeafMetadata,
+    new_owner_commitment: [u8; 32],
+    sale_price: u64,
 ) -> Result<()> {
-    require!(new_owner_id.len() <= Ticket::MAX_OWNER_ID_LEN, TicketTokenError::OwnerIdTooLong);
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Gifts need a transferable event, sales a resaleable one
+    let event = &ctx.accounts.event;
+    if sale_price == 0 {
+        require!(event.transferable, TicketTokenError::TransferNotAllowed);
+    } else {
+        require!(event.resaleable, TicketTokenError::ResaleNotAllowed);
+    }
+    
+    // The platform or venue moves custodial tickets; self-custody holders sign themselves
+    let self_custody = event.self_custody;
+    let authority = ctx.accounts.authority.key();
+    let venue = &ctx.accounts.venue;
+    let is_custodian = ctx.accounts.role_registry.authorizes(&ctx.accounts.platform.owner, &authority, Role::Custodian)
+        || VenueStaff::is_authorized(
+            &venue.key(),
+            &venue.owner,
+            &authority,
+            ctx.accounts.venue_staff.as_deref(),
+            VenueStaff::TRANSFER_TICKETS,
+            current_time,
+        );
+    require!(
+        ctx.accounts.ticket.can_act(self_custody, &authority, is_custodian),
+        TicketTokenError::NotTicketHolder
+    );
+    // Custodial tickets still sit in a real wallet, or they could never be refunded
+    let new_owner_wallet = ctx.accounts.new_leaf_owner.key();
+    require!(
+        new_owner_wallet != Pubkey::default(),
+        TicketTokenError::InvalidOwnerWallet
+    );
+    require!(!ctx.accounts.ticket.used, TicketTokenError::TicketAlreadyUsed);
+    require_keys_eq!(
+        get_asset_id(&ctx.accounts.merkle_tree.key(), leaf.nonce),
+        ctx.accounts.ticket.nft_asset_id,
+        TicketTokenError::InvalidTicket
+    );
+    
+    // Moves between two users of the same custodial wallet leave the leaf put
+    if new_owner_wallet != ctx.accounts.ticket.owner_wallet {
+        let metadata = ticket_leaf_metadata(&ctx.accounts.event, &metadata, &leaf)?;
+        transfer_ticket_leaf(
+            &TicketTransferAccounts {
+                bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+                tree_config: &ctx.accounts.tree_config.to_account_info(),
+                merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+                leaf_owner: &ctx.accounts.leaf_owner.to_account_info(),
+                new_leaf_owner: &ctx.accounts.new_leaf_owner.to_account_info(),
+                log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+                compression_program: &ctx.accounts.compression_program.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+            },
+            &metadata,
+            &leaf,
+            ctx.remaining_accounts,
+        )?;
+    }
     
     let ticket = &mut ctx.accounts.ticket;
-    require!(!ticket.used, TicketTokenError::TicketAlreadyUsed);
-    
-    let old_owner_id = ticket.current_owner_id.clone();
-    ticket.current_owner_id = new_owner_id.clone();
-    ticket.transfer_count += 1;
-    
-    msg!(
-        "Ticket {} transferred from {} to {} (transfer #{})", 
-        ticket.ticket_id,
-        old_owner_id,
-        new_owner_id,
-        ticket.transfer_count
+    let previous_wallet = ticket.owner_wallet;
+    let previous_commitment = ticket.owner_commitment;
+    ticket.provenance_hash = Ticket::provenance_step(
+        &ticket.provenance_hash,
+        &previous_commitment,
+        &new_owner_commitment,
+        sale_price,
+        current_time,
     );
+    ticket.owner_commitment = new_owner_commitment;
+    ticket.owner_wallet = new_owner_wallet;
+    ticket.transfer_count = ticket.transfer_count
+        .checked_add(1)
+        .ok_or(TicketTokenError::MathOverflow)?;
+    
+    // Zero means a gift; anything else counts towards resale volume
+    if sale_price > 0 {
+        ctx.accounts.platform.record_resale(sale_price)?;
+    }
+    
+    emit!(TicketTransferred {
+        event: ticket.event,
+        ticket: ticket.key(),
+        ticket_id: ticket.ticket_id,
+        from_wallet: previous_wallet,
+        to_wallet: new_owner_wallet,
+        previous_owner_commitment: previous_commitment,
+        owner_commitment: new_owner_commitment,
+        sale_price,
+        transfer_count: ticket.transfer_count,
+        provenance_hash: ticket.provenance_hash,
+        authority,
+        timestamp: current_time,
+    });
+    
+    msg!("Ticket {} transferred (transfer #{})", ticket.ticket_id, ticket.transfer_count);
     
     Ok(())
 }
+
+#[event]
+pub struct TicketTransferred {
+    pub event: Pubkey,
+    pub ticket: Pubkey,
+    pub ticket_id: u64,
+    pub from_wallet: Pubkey,
+    pub to_wallet: Pubkey,
+    pub previous_owner_commitment: [u8; 32],
+    pub owner_commitment: [u8; 32],
+    pub sale_price: u64,
+    pub transfer_count: u32,
+    pub provenance_hash: [u8; 32],
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::Platform;
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct UpdateTreasury<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = platform.owner == owner.key() @ TicketTokenError::Unauthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+pub fn update_treasury(ctx: Context<UpdateTreasury>, treasury: Pubkey) -> Result<()> {
+    // Same rule as initialize_platform
+    require!(
+        treasury != Pubkey::default(),
+        TicketTokenError::InvalidTreasury
+    );
+
+    let platform = &mut ctx.accounts.platform;
+    let previous_treasury = platform.treasury;
+    platform.treasury = treasury;
+
+    emit!(TreasuryUpdated {
+        previous_treasury,
+        treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Platform treasury changed from {} to {}", previous_treasury, treasury);
+
+    Ok(())
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub previous_treasury: Pubkey,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
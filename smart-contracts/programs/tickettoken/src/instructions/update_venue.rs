@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::{Platform, Venue};
+use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::string_to_bytes;
+use crate::utils::validation::validate_venue_profile;
+
+#[derive(Accounts)]
+pub struct UpdateVenue<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        constraint = venue.owner == owner.key() @ TicketTokenError::UnauthorizedVenue,
+    )]
+    pub venue: Account<'info, Venue>,
+}
+
+pub fn update_venue(
+    ctx: Context<UpdateVenue>,
+    name: String,
+    metadata_uri: String,
+) -> Result<()> {
+    validate_venue_profile(&name, &metadata_uri)?;
+    
+    let venue = &mut ctx.accounts.venue;
+    venue.name = string_to_bytes(&name, 64)?
+        .try_into()
+        .map_err(|_| TicketTokenError::VenueNameTooLong)?;
+    venue.metadata_uri = string_to_bytes(&metadata_uri, 64)?
+        .try_into()
+        .map_err(|_| TicketTokenError::UriTooLong)?;
+    
+    emit!(VenueUpdated {
+        venue: venue.key(),
+        name: name.clone(),
+        metadata_uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Venue {} profile updated to '{}'", venue.key(), name);
+    
+    Ok(())
+}
+
+#[event]
+pub struct VenueUpdated {
+    pub venue: Pubkey,
+    pub name: String,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+}
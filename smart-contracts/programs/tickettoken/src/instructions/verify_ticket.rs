@@ -1,17 +1,37 @@
 use anchor_lang::prelude::*;
-use crate::state::{Event, Ticket};
+use crate::state::{Platform, Venue, Event, Ticket, VenueStaff};
 use crate::errors::TicketTokenError;
+use crate::constants::*;
+use crate::utils::{string_to_bytes, validate_string};
 
 #[derive(Accounts)]
 pub struct VerifyTicket<'info> {
     pub validator: Signer<'info>,
     
     #[account(
-        constraint = event.start_time - 3600 <= Clock::get()?.unix_timestamp @ TicketTokenError::EventAlreadyStarted,
-        constraint = Clock::get()?.unix_timestamp <= event.end_time + 3600 @ TicketTokenError::EventAlreadyStarted,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
     )]
+    pub platform: Account<'info, Platform>,
+    
     pub event: Account<'info, Event>,
     
+    #[account(
+        constraint = event.venue == venue.key() @ TicketTokenError::InvalidEventVenue,
+    )]
+    pub venue: Account<'info, Venue>,
+    
+    /// Staff record, required unless the validator is the venue or platform owner
+    #[account(
+        seeds = [VENUE_STAFF_SEED, venue.key().as_ref(), validator.key().as_ref()],
+        bump = venue_staff.bump,
+    )]
+    pub venue_staff: Option<Account<'info, VenueStaff>>,
+    
+    /// Holder wallet answering the check-in challenge on self-custody events
+    pub holder: Option<Signer<'info>>,
+    
     #[account(
         mut,
         seeds = [b"ticket", event.key().as_ref(), ticket.ticket_id.to_le_bytes().as_ref()],
@@ -21,11 +41,39 @@ pub struct VerifyTicket<'info> {
     pub ticket: Account<'info, Ticket>,
 }
 
-pub fn verify_ticket(ctx: Context<VerifyTicket>) -> Result<()> {
+pub fn verify_ticket(ctx: Context<VerifyTicket>, gate: String) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    // Cancelled tickets are refunded, not admitted
+    require!(!ctx.accounts.event.cancelled, TicketTokenError::EventCancelled);
+    ctx.accounts.event.validate_check_in(current_time)?;
+    
+    // Only the venue's scanners (or the platform) may burn a ticket at the door
+    let validator_key = ctx.accounts.validator.key();
+    let venue = &ctx.accounts.venue;
+    require!(
+        validator_key == ctx.accounts.platform.owner
+            || VenueStaff::is_authorized(
+                &venue.key(),
+                &venue.owner,
+                &validator_key,
+                ctx.accounts.venue_staff.as_deref(),
+                VenueStaff::SCAN_TICKETS,
+                current_time,
+            ),
+        TicketTokenError::UnauthorizedScanner
+    );
+    if ctx.accounts.event.self_custody {
+        require!(
+            ctx.accounts.holder.as_ref().is_some_and(|holder| holder.key() == ctx.accounts.ticket.owner_wallet),
+            TicketTokenError::HolderSignatureRequired
+        );
+    }
+    validate_string(&gate)?;
+    let gate_bytes = string_to_bytes(&gate, MAX_GATE_NAME)?;
+    
     // Capture keys before mutable borrow
     let event_key = ctx.accounts.event.key();
     let ticket_key = ctx.accounts.ticket.key();
-    let validator_key = ctx.accounts.validator.key();
     
     let ticket = &mut ctx.accounts.ticket;
     
@@ -34,22 +82,23 @@ pub fn verify_ticket(ctx: Context<VerifyTicket>) -> Result<()> {
     
     // Mark ticket as used (immutable!)
     ticket.used = true;
-    ticket.verified_at = Some(Clock::get()?.unix_timestamp);
+    ticket.verified_at = Some(current_time);
+    ticket.verified_gate.copy_from_slice(&gate_bytes);
     
     let ticket_id = ticket.ticket_id;
-    let owner = ticket.current_owner_id.clone();
-    let timestamp = ticket.verified_at.unwrap();
+    let owner_commitment = ticket.owner_commitment;
     
     emit!(TicketVerified {
         event: event_key,
         ticket: ticket_key,
         ticket_id,
-        owner,
+        owner_commitment,
         validator: validator_key,
-        timestamp,
+        gate: gate.clone(),
+        timestamp: current_time,
     });
     
-    msg!("Ticket {} verified and marked as USED", ticket_id);
+    msg!("Ticket {} verified at gate {} and marked as USED", ticket_id, gate);
     
     Ok(())
 }
@@ -59,7 +108,8 @@ pub struct TicketVerified {
     pub event: Pubkey,
     pub ticket: Pubkey,
     pub ticket_id: u64,
-    pub owner: String,
+    pub owner_commitment: [u8; 32],
     pub validator: Pubkey,
+    pub gate: String,
     pub timestamp: i64,
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{Platform, Venue};
+use crate::state::{Platform, Venue, Role, RoleRegistry};
 use crate::errors::TicketTokenError;
 use crate::constants::*;
 
@@ -11,10 +11,17 @@ pub struct VerifyVenue<'info> {
     #[account(
         seeds = [PLATFORM_SEED],
         bump = platform.bump,
-        constraint = platform.owner == authority.key() @ TicketTokenError::Unauthorized
+        constraint = !platform.paused @ TicketTokenError::PlatformPaused,
     )]
     pub platform: Account<'info, Platform>,
     
+    #[account(
+        seeds = [ROLE_REGISTRY_SEED],
+        bump = role_registry.bump,
+        constraint = role_registry.authorizes(&platform.owner, &authority.key(), Role::VenueVerifier) @ TicketTokenError::Unauthorized,
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
     #[account(
         mut,
         constraint = !venue.verified @ TicketTokenError::AlreadyVerified
@@ -25,6 +32,7 @@ pub struct VerifyVenue<'info> {
 pub fn verify_venue(ctx: Context<VerifyVenue>) -> Result<()> {
     let venue = &mut ctx.accounts.venue;
     venue.verified = true;
+    venue.revocation_reason = 0;
     
     emit!(VenueVerified {
         venue: ctx.accounts.venue.key(),
@@ -9,6 +9,13 @@ pub mod utils;
 use instructions::*;
 use state::MintTicketArgs;
 use state::CreateEventParams;
+use state::CreateTicketTierParams;
+use state::TicketLeafArgs;
+use state::TicketLeafMetadata;
+use state::Role;
+use state::AdminOperationKind;
+use state::PlatformStats;
+use state::PayoutWallet;
 
 declare_id!("BnYanHjkV6bBDFYfC7F76TyYk6NA9p3wvcAfY1XZCXYS");
 
@@ -24,6 +31,53 @@ pub mod tickettoken {
         instructions::initialize_platform::initialize_platform(ctx, fee_bps, treasury)
     }
 
+    pub fn propose_platform_owner(ctx: Context<ProposePlatformOwner>, new_owner: Pubkey) -> Result<()> {
+        instructions::propose_platform_owner::propose_platform_owner(ctx, new_owner)
+    }
+
+    pub fn cancel_platform_owner_transfer(ctx: Context<ProposePlatformOwner>) -> Result<()> {
+        instructions::propose_platform_owner::cancel_platform_owner_transfer(ctx)
+    }
+
+    pub fn accept_platform_owner(ctx: Context<AcceptPlatformOwner>) -> Result<()> {
+        instructions::accept_platform_owner::accept_platform_owner(ctx)
+    }
+
+    pub fn update_treasury(ctx: Context<UpdateTreasury>, treasury: Pubkey) -> Result<()> {
+        instructions::update_treasury::update_treasury(ctx, treasury)
+    }
+
+    pub fn initialize_role_registry(ctx: Context<InitializeRoleRegistry>) -> Result<()> {
+        instructions::initialize_role_registry::initialize_role_registry(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<ManageRole>, member: Pubkey, role: Role) -> Result<()> {
+        instructions::grant_role::grant_role(ctx, member, role)
+    }
+
+    pub fn revoke_role(ctx: Context<ManageRole>, member: Pubkey, role: Role) -> Result<()> {
+        instructions::grant_role::revoke_role(ctx, member, role)
+    }
+
+    pub fn queue_admin_operation(
+        ctx: Context<QueueAdminOperation>,
+        kind: AdminOperationKind,
+    ) -> Result<()> {
+        instructions::queue_admin_operation::queue_admin_operation(ctx, kind)
+    }
+
+    pub fn execute_admin_operation(ctx: Context<ExecuteAdminOperation>) -> Result<()> {
+        instructions::execute_admin_operation::execute_admin_operation(ctx)
+    }
+
+    pub fn cancel_admin_operation(ctx: Context<CancelAdminOperation>) -> Result<()> {
+        instructions::cancel_admin_operation::cancel_admin_operation(ctx)
+    }
+
+    pub fn get_platform_stats(ctx: Context<GetPlatformStats>) -> Result<PlatformStats> {
+        instructions::get_platform_stats::get_platform_stats(ctx)
+    }
+
     pub fn create_venue(
         ctx: Context<CreateVenue>,
         venue_id: String,
@@ -37,6 +91,42 @@ pub mod tickettoken {
         instructions::verify_venue::verify_venue(ctx)
     }
 
+    pub fn update_venue(
+        ctx: Context<UpdateVenue>,
+        name: String,
+        metadata_uri: String,
+    ) -> Result<()> {
+        instructions::update_venue::update_venue(ctx, name, metadata_uri)
+    }
+
+    pub fn set_venue_active(ctx: Context<SetVenueActive>, active: bool) -> Result<()> {
+        instructions::set_venue_active::set_venue_active(ctx, active)
+    }
+
+    pub fn revoke_venue_verification(
+        ctx: Context<RevokeVenueVerification>,
+        reason: u16,
+    ) -> Result<()> {
+        instructions::revoke_venue_verification::revoke_venue_verification(ctx, reason)
+    }
+
+    pub fn set_venue_payout(ctx: Context<SetVenuePayout>, wallets: Vec<PayoutWallet>) -> Result<()> {
+        instructions::set_venue_payout::set_venue_payout(ctx, wallets)
+    }
+
+    pub fn add_venue_staff(
+        ctx: Context<AddVenueStaff>,
+        staff: Pubkey,
+        permissions: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::add_venue_staff::add_venue_staff(ctx, staff, permissions, expires_at)
+    }
+
+    pub fn remove_venue_staff(ctx: Context<RemoveVenueStaff>) -> Result<()> {
+        instructions::remove_venue_staff::remove_venue_staff(ctx)
+    }
+
     pub fn create_event(
         ctx: Context<CreateEvent>,
         params: CreateEventParams,
@@ -44,8 +134,85 @@ pub mod tickettoken {
         instructions::create_event::create_event(ctx, params)
     }
 
-    pub fn purchase_tickets(
-        ctx: Context<PurchaseTickets>,
+    pub fn initialize_event_tree(
+        ctx: Context<InitializeEventTree>,
+        capacity: u32,
+        max_proof_bytes: u16,
+    ) -> Result<()> {
+        instructions::initialize_event_tree::initialize_event_tree(ctx, capacity, max_proof_bytes)
+    }
+
+    pub fn create_event_collection(ctx: Context<CreateEventCollection>) -> Result<()> {
+        instructions::create_event_collection::create_event_collection(ctx)
+    }
+
+    pub fn initialize_seat_map(
+        ctx: Context<InitializeSeatMap>,
+        section: String,
+        rows: u16,
+        seats_per_row: u16,
+        tiers: u8,
+    ) -> Result<()> {
+        instructions::initialize_seat_map::initialize_seat_map(ctx, section, rows, seats_per_row, tiers)
+    }
+
+    pub fn create_ticket_tier(
+        ctx: Context<CreateTicketTier>,
+        params: CreateTicketTierParams,
+    ) -> Result<()> {
+        instructions::create_ticket_tier::create_ticket_tier(ctx, params)
+    }
+
+    pub fn set_checkin_window(
+        ctx: Context<SetCheckinWindow>,
+        doors_open: i64,
+        checkin_close: i64,
+    ) -> Result<()> {
+        instructions::set_checkin_window::set_checkin_window(ctx, doors_open, checkin_close)
+    }
+
+    pub fn set_refund_policy(ctx: Context<SetRefundPolicy>, refund_fee_on_cancel: bool) -> Result<()> {
+        instructions::set_refund_policy::set_refund_policy(ctx, refund_fee_on_cancel)
+    }
+
+    pub fn pause_platform(ctx: Context<PausePlatform>) -> Result<()> {
+        instructions::pause_platform::pause_platform(ctx)
+    }
+
+    pub fn unpause_platform(ctx: Context<PausePlatform>) -> Result<()> {
+        instructions::pause_platform::unpause_platform(ctx)
+    }
+
+    pub fn cancel_event(ctx: Context<CancelEvent>) -> Result<()> {
+        instructions::cancel_event::cancel_event(ctx)
+    }
+
+    pub fn claim_refund<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRefund<'info>>,
+        leaf: TicketLeafArgs,
+        metadata: TicketLeafMetadata,
+    ) -> Result<()> {
+        instructions::claim_refund::claim_refund(ctx, leaf, metadata)
+    }
+
+    pub fn refund_ticket<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefundTicket<'info>>,
+        leaf: TicketLeafArgs,
+        metadata: TicketLeafMetadata,
+    ) -> Result<()> {
+        instructions::refund_ticket::refund_ticket(ctx, leaf, metadata)
+    }
+
+    pub fn set_dispute_hold(ctx: Context<SetDisputeHold>, on_hold: bool) -> Result<()> {
+        instructions::set_dispute_hold::set_dispute_hold(ctx, on_hold)
+    }
+
+    pub fn settle_event(ctx: Context<SettleEvent>) -> Result<()> {
+        instructions::settle_event::settle_event(ctx)
+    }
+
+    pub fn purchase_tickets<'info>(
+        ctx: Context<'_, '_, '_, 'info, PurchaseTickets<'info>>,
         args: MintTicketArgs,
     ) -> Result<()> {
         instructions::purchase_tickets::handler(ctx, args)
@@ -60,24 +227,27 @@ pub mod tickettoken {
         instructions::list_ticket_on_marketplace(ctx, ticket_asset_id, price, expires_at)
     }
 
-    pub fn register_ticket(
-        ctx: Context<RegisterTicket>,
-        ticket_id: u64,
-        nft_asset_id: Pubkey,
-        owner_id: String,
+    pub fn register_ticket<'info>(
+        ctx: Context<'_, '_, '_, 'info, RegisterTicket<'info>>,
+        leaf: TicketLeafArgs,
+        metadata: TicketLeafMetadata,
+        owner_commitment: [u8; 32],
     ) -> Result<()> {
-        instructions::register_ticket::register_ticket(ctx, ticket_id, nft_asset_id, owner_id)
+        instructions::register_ticket::register_ticket(ctx, leaf, metadata, owner_commitment)
     }
 
-    pub fn transfer_ticket(
-        ctx: Context<TransferTicket>,
-        new_owner_id: String,
+    pub fn transfer_ticket<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferTicket<'info>>,
+        leaf: TicketLeafArgs,
+        metadata: TicketLeafMetadata,
+        new_owner_commitment: [u8; 32],
+        sale_price: u64,
     ) -> Result<()> {
-        instructions::transfer_ticket::transfer_ticket(ctx, new_owner_id)
+        instructions::transfer_ticket::transfer_ticket(ctx, leaf, metadata, new_owner_commitment, sale_price)
     }
 
-    pub fn verify_ticket(ctx: Context<VerifyTicket>) -> Result<()> {
-        instructions::verify_ticket::verify_ticket(ctx)
+    pub fn verify_ticket(ctx: Context<VerifyTicket>, gate: String) -> Result<()> {
+        instructions::verify_ticket::verify_ticket(ctx, gate)
     }
 }
 
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_TIMELOCK_DELAY, MIN_TIMELOCK_DELAY, PLATFORM_FEE_CAP};
+use crate::errors::TicketTokenError;
+use crate::state::{Platform, Role};
+
+/// A platform parameter change that has to wait out the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AdminOperationKind {
+    SetFeeBps { fee_bps: u16 },
+    SetTimelockDelay { delay: i64 },
+}
+
+impl AdminOperationKind {
+    pub const SIZE: usize = 1 + 8;
+
+    /// Role allowed to queue this change; `None` means owner only
+    pub fn required_role(&self) -> Option<Role> {
+        match self {
+            AdminOperationKind::SetFeeBps { .. } => Some(Role::FeeManager),
+            AdminOperationKind::SetTimelockDelay { .. } => None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            AdminOperationKind::SetFeeBps { fee_bps } => {
+                require!(fee_bps <= PLATFORM_FEE_CAP, TicketTokenError::FeeTooHigh);
+            }
+            AdminOperationKind::SetTimelockDelay { delay } => {
+                require!(
+                    (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&delay),
+                    TicketTokenError::InvalidTimelockDelay
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, platform: &mut Platform) -> Result<()> {
+        self.validate()?;
+        match *self {
+            AdminOperationKind::SetFeeBps { fee_bps } => platform.fee_bps = fee_bps,
+            AdminOperationKind::SetTimelockDelay { delay } => platform.timelock_delay = delay,
+        }
+        Ok(())
+    }
+}
+
+/// Queued admin change, one PDA per operation id. Kept after execution
+/// or cancellation as an audit record.
+#[account]
+pub struct AdminOperation {
+    pub id: u64,                    // 8 bytes - Sequence number from Platform
+    pub kind: AdminOperationKind,   // 9 bytes - Change to apply
+    pub proposer: Pubkey,           // 32 bytes - Key that queued it
+    pub proposed_at: i64,           // 8 bytes - Unix timestamp
+    pub executable_at: i64,         // 8 bytes - Earliest execution time
+    pub executed: bool,             // 1 byte - Applied to the platform
+    pub cancelled: bool,            // 1 byte - Withdrawn before execution
+    pub bump: u8,                   // 1 byte - PDA bump
+}
+
+impl AdminOperation {
+    pub const SIZE: usize = 8 + AdminOperationKind::SIZE + 32 + 8 + 8 + 1 + 1 + 1;
+
+    pub fn is_pending(&self) -> bool {
+        !self.executed && !self.cancelled
+    }
+
+    pub fn is_ready(&self, now: i64) -> bool {
+        self.is_pending() && now >= self.executable_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_change_validation() {
+        assert!(AdminOperationKind::SetFeeBps { fee_bps: 500 }.validate().is_ok());
+        assert!(AdminOperationKind::SetFeeBps { fee_bps: PLATFORM_FEE_CAP + 1 }.validate().is_err());
+        assert!(AdminOperationKind::SetTimelockDelay { delay: MIN_TIMELOCK_DELAY - 1 }.validate().is_err());
+        assert!(AdminOperationKind::SetTimelockDelay { delay: MAX_TIMELOCK_DELAY + 1 }.validate().is_err());
+    }
+
+    #[test]
+    fn test_apply_operation() {
+        let mut platform = Platform::default();
+        AdminOperationKind::SetFeeBps { fee_bps: 300 }.apply(&mut platform).unwrap();
+        assert_eq!(platform.fee_bps, 300);
+
+        AdminOperationKind::SetTimelockDelay { delay: MAX_TIMELOCK_DELAY }.apply(&mut platform).unwrap();
+        assert_eq!(platform.timelock_delay, MAX_TIMELOCK_DELAY);
+    }
+
+    #[test]
+    fn test_operation_readiness() {
+        let mut op = AdminOperation {
+            id: 0,
+            kind: AdminOperationKind::SetFeeBps { fee_bps: 300 },
+            proposer: Pubkey::new_unique(),
+            proposed_at: 1_000,
+            executable_at: 2_000,
+            executed: false,
+            cancelled: false,
+            bump: 255,
+        };
+
+        assert!(!op.is_ready(1_999));
+        assert!(op.is_ready(2_000));
+
+        op.cancelled = true;
+        assert!(!op.is_ready(3_000));
+    }
+}
@@ -1,12 +1,35 @@
 use anchor_lang::prelude::*;
 use crate::errors::TicketTokenError;
+use crate::constants::MAX_REFUND_STEPS;
+
+/// One step of an event's refund schedule: until `cutoff` seconds before
+/// the start time, holders get `refund_bps` of the ticket price back.
+/// A negative cutoff reaches past the start, up to the refund window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct RefundStep {
+    pub cutoff: i64,
+    pub refund_bps: u16,
+}
+
+impl RefundStep {
+    pub const SIZE: usize = 8 + 2;
+
+    /// Refund owed at `now` under `schedule` for an event starting at `start_time`
+    pub fn refund_bps_at(schedule: &[RefundStep], start_time: i64, now: i64) -> u16 {
+        schedule
+            .iter()
+            .find(|step| now < start_time.saturating_sub(step.cutoff))
+            .map(|step| step.refund_bps)
+            .unwrap_or(0)
+    }
+}
 
 #[account]
 pub struct Event {
     pub venue: Pubkey,                // 32 bytes - Parent venue
     pub event_id: u64,                // 8 bytes - Unique event ID
     pub name: [u8; 32],               // 32 bytes - Event name
-    pub ticket_price: u64,            // 8 bytes - Price in lamports
+    pub ticket_price: u64,            // 8 bytes - Legacy list price, sales use the tier price
     pub total_tickets: u32,           // 4 bytes - Total capacity
     pub tickets_sold: u32,            // 4 bytes - Current sold count
     pub tickets_reserved: u32,        // 4 bytes - Reserved (not paid)
@@ -23,6 +46,21 @@ pub struct Event {
     pub artist_percentage: u16,       // 2 bytes - Basis points (500 = 5%)
     pub venue_percentage: u16,        // 2 bytes - Basis points (500 = 5%)
     pub bump: u8,                     // 1 byte - PDA bump seed
+    pub tree_count: u8,               // 1 byte - EventTree accounts created
+    pub active_tree: u8,              // 1 byte - First tree with free leaves
+    pub collection_mint: Pubkey,      // 32 bytes - Verified ticket collection
+    pub tier_count: u8,               // 1 byte - TicketTier accounts created
+    pub cancelled: bool,              // 1 byte - Refunds open, sales closed
+    pub next_ticket_number: u32,      // 4 bytes - Never reused after refunds
+    pub refund_steps: u8,             // 1 byte - Used entries in refund_schedule
+    pub refund_schedule: [RefundStep; MAX_REFUND_STEPS], // 40 bytes
+    pub settled: bool,                // 1 byte - Proceeds paid out
+    pub dispute_hold: bool,           // 1 byte - Settlement blocked by platform
+    pub platform_fee_bps: u16,        // 2 bytes - Platform fee when the event was created
+    pub doors_open: i64,              // 8 bytes - Check-in opens this many seconds before start
+    pub checkin_close: i64,           // 8 bytes - Check-in closes this many seconds after end
+    pub self_custody: bool,           // 1 byte - Holders sign transfers and check-in, not the backend
+    pub refund_fee_on_cancel: bool,   // 1 byte - Platform refund policy when the event was cancelled
 }
 
 impl Event {
@@ -46,8 +84,23 @@ impl Event {
         32 +                          // artist_wallet
         2 +                           // artist_percentage
         2 +                           // venue_percentage
-        1;                            // bump
-    // Total: 491 bytes (updated to include royalty fields)
+        1 +                           // bump
+        1 +                           // tree_count
+        1 +                           // active_tree
+        32 +                          // collection_mint
+        1 +                           // tier_count
+        1 +                           // cancelled
+        4 +                           // next_ticket_number
+        1 +                           // refund_steps
+        RefundStep::SIZE * MAX_REFUND_STEPS + // refund_schedule
+        1 +                           // settled
+        1 +                           // dispute_hold
+        2 +                           // platform_fee_bps
+        8 +                           // doors_open
+        8 +                           // checkin_close
+        1 +                           // self_custody
+        1;                            // refund_fee_on_cancel
+    // Total: 594 bytes (updated to include cancellation policy)
 
     pub fn is_active(&self) -> Result<bool> {
         let now = Clock::get().map_err(|_| TicketTokenError::ClockError)?.unix_timestamp;
@@ -58,4 +111,95 @@ impl Event {
         let now = Clock::get().map_err(|_| TicketTokenError::ClockError)?.unix_timestamp;
         Ok(now < self.start_time.saturating_add(self.refund_window))
     }
+
+    /// Tickets can be scanned from `doors_open` seconds before the start
+    /// until `checkin_close` seconds after the end
+    pub fn validate_check_in(&self, now: i64) -> Result<()> {
+        require!(
+            now >= self.start_time.saturating_sub(self.doors_open),
+            TicketTokenError::CheckInNotOpen
+        );
+        require!(
+            now <= self.end_time.saturating_add(self.checkin_close),
+            TicketTokenError::CheckInClosed
+        );
+        Ok(())
+    }
+
+    /// Share of the ticket price refunded at `now`. Without a schedule the
+    /// full price is refunded for as long as `can_refund` allows.
+    pub fn refund_bps(&self, now: i64) -> u16 {
+        if self.refund_steps == 0 {
+            return 10_000;
+        }
+        RefundStep::refund_bps_at(
+            &self.refund_schedule[..self.refund_steps as usize],
+            self.start_time,
+            now,
+        )
+    }
+
+    /// Steps must have strictly later deadlines and never-increasing refunds
+    pub fn validate_refund_schedule(schedule: &[RefundStep]) -> Result<()> {
+        require!(
+            schedule.len() <= MAX_REFUND_STEPS,
+            TicketTokenError::InvalidRefundSchedule
+        );
+        for (i, step) in schedule.iter().enumerate() {
+            require!(
+                step.refund_bps <= 10_000,
+                TicketTokenError::InvalidRefundSchedule
+            );
+            if i > 0 {
+                let previous = &schedule[i - 1];
+                require!(
+                    step.cutoff < previous.cutoff && step.refund_bps <= previous.refund_bps,
+                    TicketTokenError::InvalidRefundSchedule
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Event {
+    fn default() -> Self {
+        Self {
+            venue: Pubkey::default(),
+            event_id: 0,
+            name: [0u8; 32],
+            ticket_price: 0,
+            total_tickets: 0,
+            tickets_sold: 0,
+            tickets_reserved: 0,
+            start_time: 0,
+            end_time: 0,
+            refund_window: 0,
+            metadata_uri: [0u8; 64],
+            oracle_feed: Pubkey::default(),
+            description: [0u8; 200],
+            transferable: false,
+            resaleable: false,
+            merkle_tree: Pubkey::default(),
+            artist_wallet: Pubkey::default(),
+            artist_percentage: 0,
+            venue_percentage: 0,
+            bump: 0,
+            tree_count: 0,
+            active_tree: 0,
+            collection_mint: Pubkey::default(),
+            tier_count: 0,
+            cancelled: false,
+            next_ticket_number: 0,
+            refund_steps: 0,
+            refund_schedule: [RefundStep::default(); MAX_REFUND_STEPS],
+            settled: false,
+            dispute_hold: false,
+            platform_fee_bps: 0,
+            doors_open: 0,
+            checkin_close: 0,
+            self_custody: false,
+            refund_fee_on_cancel: false,
+        }
+    }
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::errors::TicketTokenError;
+
+/// One of the compressed NFT trees owned by an event. Trees are filled in
+/// index order; `Event.active_tree` points at the first one with free leaves.
+/// Purchases mint consecutive ticket numbers into a tree, so leaf `n` holds
+/// ticket `first_ticket + n`.
+#[account]
+#[derive(Default)]
+pub struct EventTree {
+    pub event: Pubkey,              // 32 bytes - Parent event PDA
+    pub index: u8,                  // 1 byte - Position in the event's tree list
+    pub merkle_tree: Pubkey,        // 32 bytes - Concurrent merkle tree account
+    pub capacity: u32,              // 4 bytes - 2^max_depth leaves
+    pub minted: u32,                // 4 bytes - Leaves minted by this program
+    pub first_ticket: u32,          // 4 bytes - Ticket number of leaf 0
+    pub bump: u8,                   // 1 byte - PDA bump
+    pub refunded: Vec<u8>,          // 4 + ceil(capacity / 8) bytes - Bit n set once leaf n is refunded
+}
+
+impl EventTree {
+    pub const BASE_SIZE: usize = 32 + 1 + 32 + 4 + 4 + 4 + 1 + 4;
+
+    pub fn size(capacity: u32) -> usize {
+        Self::BASE_SIZE + Self::bitmap_len(capacity)
+    }
+
+    pub fn bitmap_len(capacity: u32) -> usize {
+        (capacity as usize).div_ceil(8)
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.capacity.saturating_sub(self.minted)
+    }
+
+    /// Leaf nonce of `ticket_id`, if the ticket was minted into this tree
+    pub fn nonce_of(&self, ticket_id: u64) -> Option<u64> {
+        let nonce = ticket_id.checked_sub(self.first_ticket as u64)?;
+        (nonce < self.minted as u64).then_some(nonce)
+    }
+
+    pub fn is_refunded(&self, nonce: u64) -> bool {
+        self.refunded
+            .get(nonce as usize / 8)
+            .is_some_and(|byte| byte & (1 << (nonce % 8)) != 0)
+    }
+
+    /// Record that the leaf was burned for a refund, so its ticket can't
+    /// be registered again
+    pub fn mark_refunded(&mut self, nonce: u64) -> Result<()> {
+        require!(nonce < self.minted as u64, TicketTokenError::InvalidTicket);
+        let byte = self.refunded
+            .get_mut(nonce as usize / 8)
+            .ok_or(TicketTokenError::InvalidTicket)?;
+        *byte |= 1 << (nonce % 8);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_nonces() {
+        // Second tree of an event: tickets #8..#10 landed on leaves 0..2
+        let mut tree = EventTree {
+            capacity: 16,
+            minted: 3,
+            first_ticket: 8,
+            refunded: vec![0u8; EventTree::bitmap_len(16)],
+            ..Default::default()
+        };
+        assert_eq!(EventTree::size(16), EventTree::BASE_SIZE + 2);
+        assert_eq!(tree.nonce_of(7), None);
+        assert_eq!(tree.nonce_of(8), Some(0));
+        assert_eq!(tree.nonce_of(10), Some(2));
+        assert_eq!(tree.nonce_of(11), None);
+
+        tree.mark_refunded(2).unwrap();
+        assert!(tree.is_refunded(2));
+        assert!(!tree.is_refunded(1));
+        assert!(tree.mark_refunded(3).is_err());
+
+        // An empty tree holds no tickets
+        assert_eq!(EventTree::default().nonce_of(0), None);
+    }
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::errors::TicketTokenError;
+
+/// Escrow for an event's primary-sale proceeds. Lamports sit on this PDA
+/// until the event settles, or flow back to buyers if it is cancelled.
+#[account]
+#[derive(Default)]
+pub struct EventVault {
+    pub event: Pubkey,              // 32 bytes - Parent event PDA
+    pub gross_sales: u64,           // 8 bytes - Lamports paid in by buyers
+    pub fees_collected: u64,        // 8 bytes - Platform fee share of gross_sales
+    pub refunded: u64,              // 8 bytes - Lamports paid back to buyers
+    pub fees_refunded: u64,         // 8 bytes - Platform fee share of refunded
+    pub paid_out: u64,              // 8 bytes - Lamports released at settlement
+    pub bump: u8,                   // 1 byte - PDA bump
+}
+
+impl EventVault {
+    pub const SIZE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Sale proceeds still held for the event
+    pub fn balance(&self) -> u64 {
+        self.gross_sales
+            .saturating_sub(self.refunded)
+            .saturating_sub(self.paid_out)
+    }
+
+    /// Platform fees still held for the event
+    pub fn fees_held(&self) -> u64 {
+        self.fees_collected.saturating_sub(self.fees_refunded)
+    }
+
+    pub fn record_sale(&mut self, amount: u64, fee: u64) -> Result<()> {
+        self.gross_sales = self.gross_sales
+            .checked_add(amount)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        self.fees_collected = self.fees_collected
+            .checked_add(fee)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_refund(&mut self, amount: u64, fee: u64) -> Result<()> {
+        require!(
+            amount <= self.balance() && fee <= self.fees_held(),
+            TicketTokenError::InsufficientVaultBalance
+        );
+        self.refunded = self.refunded
+            .checked_add(amount)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        self.fees_refunded = self.fees_refunded
+            .checked_add(fee)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Release everything still held once the event settles
+    pub fn record_settlement(&mut self) -> Result<u64> {
+        let amount = self.balance();
+        self.paid_out = self.paid_out
+            .checked_add(amount)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        Ok(amount)
+    }
+
+    /// Move lamports out of the vault. The vault is owned by this program,
+    /// so it can be debited directly without a system transfer.
+    pub fn pay_out(vault: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+        let vault_lamports = vault.lamports();
+        **vault.try_borrow_mut_lamports()? = vault_lamports
+            .checked_sub(amount)
+            .ok_or(TicketTokenError::InsufficientVaultBalance)?;
+        let to_lamports = to.lamports();
+        **to.try_borrow_mut_lamports()? = to_lamports
+            .checked_add(amount)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_vault_accounting() {
+        let mut vault = EventVault::default();
+        vault.record_sale(3_000, 75).unwrap();
+        assert_eq!(vault.balance(), 3_000);
+        assert_eq!(vault.fees_held(), 75);
+
+        vault.record_refund(1_000, 25).unwrap();
+        assert_eq!(vault.balance(), 2_000);
+        assert_eq!(vault.fees_held(), 50);
+
+        // Cannot refund more than is held
+        assert!(vault.record_refund(2_001, 0).is_err());
+        assert!(vault.record_refund(0, 51).is_err());
+        assert_eq!(vault.balance(), 2_000);
+    }
+
+    #[test]
+    fn test_event_vault_settlement_empties_balance() {
+        let mut vault = EventVault::default();
+        vault.record_sale(3_000, 75).unwrap();
+        vault.record_refund(1_000, 25).unwrap();
+
+        assert_eq!(vault.record_settlement().unwrap(), 2_000);
+        assert_eq!(vault.balance(), 0);
+        assert!(vault.record_refund(1, 0).is_err());
+    }
+}
@@ -4,6 +4,14 @@ pub mod venue;
 pub mod event;
 pub mod ticket;
 pub mod tree_config;
+pub mod event_tree;
+pub mod seat_map;
+pub mod ticket_tier;
+pub mod event_vault;
+pub mod settlement;
+pub mod role_registry;
+pub mod admin_operation;
+pub mod venue_staff;
 
 #[cfg(test)]
 mod tests;
@@ -13,16 +21,27 @@ pub use venue::*;
 pub use event::*;
 pub use ticket::*;
 pub use tree_config::*;
+pub use event_tree::*;
+pub use seat_map::*;
+pub use ticket_tier::*;
+pub use event_vault::*;
+pub use settlement::*;
+pub use role_registry::*;
+pub use admin_operation::*;
+pub use venue_staff::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CreateEventParams {
     pub event_id: u64,
     pub name: String,
+    /// Legacy list price kept for existing clients; buyers pay the
+    /// price of the tier they purchase in
     pub ticket_price: u64,
     pub total_tickets: u32,
     pub start_time: i64,
     pub end_time: i64,
     pub refund_window: i64,
+    pub refund_schedule: Vec<RefundStep>,
     pub metadata_uri: String,
     pub oracle_feed: Pubkey,
     pub description: String,
@@ -31,4 +50,7 @@ pub struct CreateEventParams {
     pub artist_wallet: Pubkey,
     pub artist_percentage: u16,
     pub venue_percentage: u16,
+    pub doors_open: i64,
+    pub checkin_close: i64,
+    pub self_custody: bool,
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::constants::PLATFORM_FEE_CAP;
+use crate::errors::TicketTokenError;
+use crate::utils::safe_add;
 
 #[account]
 #[derive(Default)]
@@ -11,14 +13,79 @@ pub struct Platform {
     pub total_venues: u64,         // 8 bytes
     pub total_events: u64,         // 8 bytes
     pub total_tickets_sold: u64,   // 8 bytes
-    pub total_fees_collected: u64, // 8 bytes
+    pub total_fees_collected: u64, // 8 bytes - Net of fees refunded
     pub bump: u8,                  // 1 byte
+    pub refund_fee_on_cancel: bool, // 1 byte - Return fee on cancelled events
+    pub pending_owner: Pubkey,     // 32 bytes - Proposed owner awaiting acceptance
+    pub timelock_delay: i64,       // 8 bytes - Wait before queued changes apply
+    pub next_operation_id: u64,    // 8 bytes - Id for the next AdminOperation
+    pub total_tickets_refunded: u64, // 8 bytes
+    pub total_refunded: u64,       // 8 bytes - Lamports returned to buyers
+    pub total_resales: u64,        // 8 bytes - Paid secondary transfers
+    pub total_resale_volume: u64,  // 8 bytes - Lamports across those resales
+}
+
+/// Snapshot of the platform counters returned by get_platform_stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PlatformStats {
+    pub total_venues: u64,
+    pub total_events: u64,
+    pub total_tickets_sold: u64,
+    pub total_fees_collected: u64,
+    pub total_tickets_refunded: u64,
+    pub total_refunded: u64,
+    pub total_resales: u64,
+    pub total_resale_volume: u64,
 }
 
 impl Platform {
-    pub const SIZE: usize = 32 + 32 + 2 + 1 + 8 + 8 + 8 + 8 + 1;
+    pub const SIZE: usize = 32 + 32 + 2 + 1 + 8 + 8 + 8 + 8 + 1 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8;
     
     pub fn validate_fee(&self) -> bool {
         self.fee_bps <= PLATFORM_FEE_CAP
     }
+
+    pub fn record_venue(&mut self) -> Result<()> {
+        self.total_venues = safe_add(self.total_venues, 1)?;
+        Ok(())
+    }
+
+    pub fn record_event(&mut self) -> Result<()> {
+        self.total_events = safe_add(self.total_events, 1)?;
+        Ok(())
+    }
+
+    pub fn record_sale(&mut self, tickets: u64, fees: u64) -> Result<()> {
+        self.total_tickets_sold = safe_add(self.total_tickets_sold, tickets)?;
+        self.total_fees_collected = safe_add(self.total_fees_collected, fees)?;
+        Ok(())
+    }
+
+    pub fn record_refund(&mut self, tickets: u64, amount: u64, fees_refunded: u64) -> Result<()> {
+        self.total_tickets_refunded = safe_add(self.total_tickets_refunded, tickets)?;
+        self.total_refunded = safe_add(self.total_refunded, amount)?;
+        self.total_fees_collected = self.total_fees_collected
+            .checked_sub(fees_refunded)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_resale(&mut self, price: u64) -> Result<()> {
+        self.total_resales = safe_add(self.total_resales, 1)?;
+        self.total_resale_volume = safe_add(self.total_resale_volume, price)?;
+        Ok(())
+    }
+
+    pub fn stats(&self) -> PlatformStats {
+        PlatformStats {
+            total_venues: self.total_venues,
+            total_events: self.total_events,
+            total_tickets_sold: self.total_tickets_sold,
+            total_fees_collected: self.total_fees_collected,
+            total_tickets_refunded: self.total_tickets_refunded,
+            total_refunded: self.total_refunded,
+            total_resales: self.total_resales,
+            total_resale_volume: self.total_resale_volume,
+        }
+    }
 }
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ROLE_MEMBERS;
+use crate::errors::TicketTokenError;
+
+/// Administrative roles delegated by the platform owner. The owner holds
+/// every role implicitly and is the only key that can grant or revoke them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    VenueVerifier,
+    FeeManager,
+    Pauser,
+    ComplianceOfficer,
+    TicketMinter,
+    Custodian,
+}
+
+impl Role {
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+/// A key and the roles it holds, as a bitmask of `Role::bit`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RoleMember {
+    pub key: Pubkey,
+    pub roles: u8,
+}
+
+impl RoleMember {
+    pub const SIZE: usize = 32 + 1;
+}
+
+#[account]
+pub struct RoleRegistry {
+    pub bump: u8,                   // 1 byte - PDA bump
+    pub members: Vec<RoleMember>,   // 4 + 33 * MAX_ROLE_MEMBERS bytes
+}
+
+impl RoleRegistry {
+    pub const SIZE: usize = 1 + 4 + RoleMember::SIZE * MAX_ROLE_MEMBERS;
+
+    pub fn has_role(&self, key: &Pubkey, role: Role) -> bool {
+        self.members
+            .iter()
+            .any(|member| member.key == *key && member.roles & role.bit() != 0)
+    }
+
+    /// Role check used by admin instructions; the platform owner always passes
+    pub fn authorizes(&self, owner: &Pubkey, key: &Pubkey, role: Role) -> bool {
+        key == owner || self.has_role(key, role)
+    }
+
+    pub fn grant(&mut self, key: Pubkey, role: Role) -> Result<()> {
+        require!(!self.has_role(&key, role), TicketTokenError::RoleAlreadyGranted);
+        if let Some(member) = self.members.iter_mut().find(|m| m.key == key) {
+            member.roles |= role.bit();
+            return Ok(());
+        }
+        require!(
+            self.members.len() < MAX_ROLE_MEMBERS,
+            TicketTokenError::TooManyRoleMembers
+        );
+        self.members.push(RoleMember { key, roles: role.bit() });
+        Ok(())
+    }
+
+    /// Remove a role, dropping the member once it holds none
+    pub fn revoke(&mut self, key: &Pubkey, role: Role) -> Result<()> {
+        require!(self.has_role(key, role), TicketTokenError::RoleNotGranted);
+        for member in self.members.iter_mut().filter(|m| m.key == *key) {
+            member.roles &= !role.bit();
+        }
+        self.members.retain(|m| m.roles != 0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> RoleRegistry {
+        RoleRegistry { bump: 255, members: Vec::new() }
+    }
+
+    #[test]
+    fn test_grant_and_revoke_roles() {
+        let mut registry = registry();
+        let ops = Pubkey::new_unique();
+
+        registry.grant(ops, Role::VenueVerifier).unwrap();
+        registry.grant(ops, Role::Pauser).unwrap();
+        assert_eq!(registry.members.len(), 1);
+        assert!(registry.has_role(&ops, Role::VenueVerifier));
+        assert!(registry.has_role(&ops, Role::Pauser));
+        assert!(!registry.has_role(&ops, Role::FeeManager));
+        assert!(registry.grant(ops, Role::Pauser).is_err());
+
+        registry.revoke(&ops, Role::VenueVerifier).unwrap();
+        assert!(!registry.has_role(&ops, Role::VenueVerifier));
+        assert!(registry.revoke(&ops, Role::VenueVerifier).is_err());
+
+        // Last role gone frees the slot
+        registry.revoke(&ops, Role::Pauser).unwrap();
+        assert!(registry.members.is_empty());
+    }
+
+    #[test]
+    fn test_multiple_keys_per_role() {
+        let mut registry = registry();
+        let owner = Pubkey::new_unique();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        registry.grant(first, Role::ComplianceOfficer).unwrap();
+        registry.grant(second, Role::ComplianceOfficer).unwrap();
+        assert!(registry.authorizes(&owner, &first, Role::ComplianceOfficer));
+        assert!(registry.authorizes(&owner, &second, Role::ComplianceOfficer));
+        assert!(registry.authorizes(&owner, &owner, Role::ComplianceOfficer));
+        assert!(!registry.authorizes(&owner, &Pubkey::new_unique(), Role::ComplianceOfficer));
+    }
+
+    #[test]
+    fn test_registry_capacity() {
+        let mut registry = registry();
+        for _ in 0..MAX_ROLE_MEMBERS {
+            registry.grant(Pubkey::new_unique(), Role::FeeManager).unwrap();
+        }
+        assert!(registry.grant(Pubkey::new_unique(), Role::FeeManager).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_SECTION_NAME, MAX_SEATS_PER_SECTION, MAX_TIERS_PER_EVENT};
+use crate::errors::TicketTokenError;
+
+/// Sold-seat bitmap for one section of an event. Rows and seats are
+/// numbered from 1; bit `(row - 1) * seats_per_row + (seat - 1)` is set
+/// once that seat has been bought. Only the tiers in `tiers` may sell
+/// seats in the section.
+#[account]
+#[derive(Default)]
+pub struct SeatMap {
+    pub event: Pubkey,                    // 32 bytes - Parent event PDA
+    pub section: [u8; MAX_SECTION_NAME],  // 16 bytes - Section name
+    pub rows: u16,                        // 2 bytes - Rows in the section
+    pub seats_per_row: u16,               // 2 bytes - Seats in each row
+    pub seats_sold: u32,                  // 4 bytes - Bits set in `sold`
+    pub tiers: u8,                        // 1 byte - Bit i set if tier i sells this section
+    pub bump: u8,                         // 1 byte - PDA bump
+    pub sold: Vec<u8>,                    // 4 + ceil(rows * seats / 8) bytes
+}
+
+impl SeatMap {
+    pub const BASE_SIZE: usize = 32 + MAX_SECTION_NAME + 2 + 2 + 4 + 1 + 1 + 4;
+
+    pub fn size(rows: u16, seats_per_row: u16) -> usize {
+        Self::BASE_SIZE + Self::bitmap_len(rows, seats_per_row)
+    }
+
+    pub fn bitmap_len(rows: u16, seats_per_row: u16) -> usize {
+        (rows as usize * seats_per_row as usize).div_ceil(8)
+    }
+
+    pub fn validate_layout(section: &str, rows: u16, seats_per_row: u16) -> Result<()> {
+        require!(
+            !section.is_empty()
+                && section.len() <= MAX_SECTION_NAME
+                && section.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            TicketTokenError::InvalidSeatMap
+        );
+        let seats = rows as u32 * seats_per_row as u32;
+        require!(
+            seats > 0 && seats <= MAX_SEATS_PER_SECTION,
+            TicketTokenError::InvalidSeatMap
+        );
+        Ok(())
+    }
+
+    pub fn allows_tier(&self, tier_index: u8) -> bool {
+        tier_index < MAX_TIERS_PER_EVENT && self.tiers & (1 << tier_index) != 0
+    }
+
+    fn seat_index(&self, row: u16, seat: u32) -> Result<usize> {
+        require!(
+            row >= 1 && row <= self.rows && seat >= 1 && seat <= self.seats_per_row as u32,
+            TicketTokenError::InvalidSeat
+        );
+        Ok((row as usize - 1) * self.seats_per_row as usize + (seat as usize - 1))
+    }
+
+    pub fn is_sold(&self, row: u16, seat: u32) -> Result<bool> {
+        let index = self.seat_index(row, seat)?;
+        Ok(self.sold[index / 8] & (1 << (index % 8)) != 0)
+    }
+
+    /// Claim `count` consecutive seats in one row, failing without
+    /// changes if any of them is out of range or already sold
+    pub fn claim(&mut self, row: u16, seat_start: u32, count: u32) -> Result<()> {
+        let seat_end = seat_start
+            .checked_add(count)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        for seat in seat_start..seat_end {
+            require!(!self.is_sold(row, seat)?, TicketTokenError::SeatAlreadySold);
+        }
+        for seat in seat_start..seat_end {
+            let index = self.seat_index(row, seat)?;
+            self.sold[index / 8] |= 1 << (index % 8);
+        }
+        self.seats_sold = self.seats_sold
+            .checked_add(count)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Put a refunded seat back on sale
+    pub fn release(&mut self, row: u16, seat: u32) -> Result<()> {
+        require!(self.is_sold(row, seat)?, TicketTokenError::InvalidSeat);
+        let index = self.seat_index(row, seat)?;
+        self.sold[index / 8] &= !(1 << (index % 8));
+        self.seats_sold -= 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seat_map(rows: u16, seats_per_row: u16) -> SeatMap {
+        SeatMap {
+            rows,
+            seats_per_row,
+            sold: vec![0u8; SeatMap::bitmap_len(rows, seats_per_row)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_seat_map_size() {
+        assert_eq!(SeatMap::bitmap_len(10, 20), 25);
+        assert_eq!(SeatMap::bitmap_len(3, 3), 2);
+        assert_eq!(SeatMap::size(10, 20), SeatMap::BASE_SIZE + 25);
+        // Largest section still fits a single CPI allocation
+        assert!(8 + SeatMap::size(256, 256) <= 10_240);
+    }
+
+    #[test]
+    fn test_seat_map_layout_validation() {
+        assert!(SeatMap::validate_layout("A", 10, 20).is_ok());
+        assert!(SeatMap::validate_layout("Floor_GA-1", 1, 5000).is_ok());
+        assert!(SeatMap::validate_layout("", 10, 20).is_err());
+        assert!(SeatMap::validate_layout("Section A", 10, 20).is_err());
+        assert!(SeatMap::validate_layout("A", 0, 20).is_err());
+        assert!(SeatMap::validate_layout("A", 1000, 1000).is_err());
+    }
+
+    #[test]
+    fn test_section_tiers() {
+        // GA (tier 0) and early bird (tier 2) only
+        let map = SeatMap { tiers: 0b101, ..seat_map(1, 1) };
+        assert!(map.allows_tier(0));
+        assert!(!map.allows_tier(1));
+        assert!(map.allows_tier(2));
+        assert!(!map.allows_tier(MAX_TIERS_PER_EVENT));
+    }
+
+    #[test]
+    fn test_claim_seats() {
+        let mut map = seat_map(3, 10);
+
+        map.claim(2, 4, 3).unwrap();
+        assert_eq!(map.seats_sold, 3);
+        assert!(!map.is_sold(2, 3).unwrap());
+        assert!(map.is_sold(2, 4).unwrap());
+        assert!(map.is_sold(2, 6).unwrap());
+        assert!(!map.is_sold(2, 7).unwrap());
+        assert!(!map.is_sold(1, 4).unwrap());
+    }
+
+    #[test]
+    fn test_claim_conflict_is_atomic() {
+        let mut map = seat_map(3, 10);
+        map.claim(1, 5, 1).unwrap();
+
+        // Seats 3..=6 overlap seat 5, so nothing is claimed
+        assert!(map.claim(1, 3, 4).is_err());
+        assert!(!map.is_sold(1, 3).unwrap());
+        assert!(!map.is_sold(1, 4).unwrap());
+        assert_eq!(map.seats_sold, 1);
+    }
+
+    #[test]
+    fn test_release_seat() {
+        let mut map = seat_map(3, 10);
+        map.claim(3, 1, 2).unwrap();
+
+        map.release(3, 2).unwrap();
+        assert!(map.is_sold(3, 1).unwrap());
+        assert!(!map.is_sold(3, 2).unwrap());
+        assert_eq!(map.seats_sold, 1);
+
+        // Only sold seats can be released, and the seat can be sold again
+        assert!(map.release(3, 2).is_err());
+        map.claim(3, 2, 1).unwrap();
+        assert_eq!(map.seats_sold, 2);
+    }
+
+    #[test]
+    fn test_claim_out_of_range() {
+        let mut map = seat_map(3, 10);
+
+        assert!(map.claim(0, 1, 1).is_err());
+        assert!(map.claim(4, 1, 1).is_err());
+        assert!(map.claim(1, 0, 1).is_err());
+        // Runs past the end of the row
+        assert!(map.claim(1, 9, 3).is_err());
+        assert!(!map.is_sold(1, 9).unwrap());
+        assert_eq!(map.seats_sold, 0);
+    }
+}
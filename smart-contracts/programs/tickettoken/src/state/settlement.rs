@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::errors::TicketTokenError;
+use crate::utils::calculate_fee;
+
+/// Final accounting for an event, written once by settle_event. Finance
+/// reconciles payouts against this record.
+#[account]
+pub struct Settlement {
+    pub event: Pubkey,              // 32 bytes - Settled event PDA
+    pub gross_sales: u64,           // 8 bytes - Lamports paid in by buyers
+    pub refunds: u64,               // 8 bytes - Lamports paid back to buyers
+    pub platform_payout: u64,       // 8 bytes - Fees paid to the platform treasury
+    pub artist_payout: u64,         // 8 bytes - Paid to the artist wallet
+    pub venue_payout: u64,          // 8 bytes - Paid to the venue
+    pub tickets_sold: u32,          // 4 bytes - Tickets outstanding at settlement
+    pub settled_by: Pubkey,         // 32 bytes - Signer that settled
+    pub settled_at: i64,            // 8 bytes - Unix timestamp
+    pub bump: u8,                   // 1 byte - PDA bump
+}
+
+/// How the proceeds held for an event are divided
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SettlementSplit {
+    pub platform: u64,
+    pub artist: u64,
+    pub venue: u64,
+}
+
+impl Settlement {
+    pub const SIZE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 4 + 32 + 8 + 1;
+
+    /// The platform takes its fees first, the artist takes
+    /// `artist_bps` of what is left and the venue gets the rest
+    pub fn split(balance: u64, fees_held: u64, artist_bps: u16) -> Result<SettlementSplit> {
+        let net = balance
+            .checked_sub(fees_held)
+            .ok_or(TicketTokenError::InsufficientVaultBalance)?;
+        let artist = calculate_fee(net, artist_bps)?;
+        let venue = net
+            .checked_sub(artist)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        Ok(SettlementSplit {
+            platform: fees_held,
+            artist,
+            venue,
+        })
+    }
+}
+
+impl SettlementSplit {
+    pub fn total(&self) -> Result<u64> {
+        crate::utils::safe_add(crate::utils::safe_add(self.platform, self.artist)?, self.venue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settlement_split() {
+        // 10 SOL held, 0.25 SOL of it platform fees, artist on 5%
+        let split = Settlement::split(10_000_000_000, 250_000_000, 500).unwrap();
+
+        assert_eq!(split.platform, 250_000_000);
+        assert_eq!(split.artist, 487_500_000);
+        assert_eq!(split.venue, 9_262_500_000);
+        assert_eq!(split.total().unwrap(), 10_000_000_000);
+    }
+
+    #[test]
+    fn test_settlement_split_without_artist() {
+        let split = Settlement::split(1_000, 30, 0).unwrap();
+        assert_eq!(split, SettlementSplit { platform: 30, artist: 0, venue: 970 });
+    }
+
+    #[test]
+    fn test_settlement_split_rejects_fees_over_balance() {
+        assert!(Settlement::split(100, 101, 0).is_err());
+    }
+}
@@ -1,20 +1,45 @@
 use crate::constants::MAX_BATCH_MINT;
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::state::{Platform, Venue};
     use crate::utils::*;
 
     #[test]
     fn test_platform_size() {
-        // Platform should be exactly 76 bytes of data + 8 bytes discriminator
-        assert_eq!(Platform::SIZE, 100);
-        println!("✅ Platform size: {} bytes (76 data + 8 discriminator)", Platform::SIZE);
+        assert_eq!(Platform::SIZE, 181);
+        println!("✅ Platform size: {} data bytes (+ 8 discriminator)", Platform::SIZE);
+    }
+
+    #[test]
+    fn test_platform_counters() {
+        let mut platform = Platform::default();
+        platform.record_venue().unwrap();
+        platform.record_event().unwrap();
+        platform.record_sale(4, 100).unwrap();
+        platform.record_refund(1, 1_000, 25).unwrap();
+        platform.record_resale(1_100).unwrap();
+
+        let stats = platform.stats();
+        assert_eq!(stats.total_venues, 1);
+        assert_eq!(stats.total_events, 1);
+        assert_eq!(stats.total_tickets_sold, 4);
+        assert_eq!(stats.total_fees_collected, 75);
+        assert_eq!(stats.total_tickets_refunded, 1);
+        assert_eq!(stats.total_refunded, 1_000);
+        assert_eq!(stats.total_resales, 1);
+        assert_eq!(stats.total_resale_volume, 1_100);
+
+        // Counters never wrap
+        platform.total_tickets_sold = u64::MAX;
+        assert!(platform.record_sale(1, 0).is_err());
+        assert!(platform.record_refund(0, 0, 76).is_err());
     }
 
     #[test]
     fn test_venue_size() {
-        // Venue is 211 bytes of data + 8 bytes discriminator = 219 total
-        assert_eq!(Venue::SIZE, 211);
+        // Venue is 348 bytes of data + 8 bytes discriminator = 356 total
+        assert_eq!(Venue::SIZE, 348);
         println!("✅ Venue size: {} bytes", Venue::SIZE);
     }
 
@@ -72,12 +97,41 @@ mod tests {
         
         println!("✅ Math overflow protection working correctly");
     }
+
+    #[test]
+    fn test_refund_schedule() {
+        use crate::state::{Event, RefundStep};
+
+        const DAY: i64 = 24 * 3600;
+        let start = 100 * DAY;
+        let schedule = [
+            RefundStep { cutoff: 7 * DAY, refund_bps: 10_000 },
+            RefundStep { cutoff: 0, refund_bps: 5_000 },
+        ];
+
+        assert!(Event::validate_refund_schedule(&schedule).is_ok());
+        assert_eq!(RefundStep::refund_bps_at(&schedule, start, start - 8 * DAY), 10_000);
+        assert_eq!(RefundStep::refund_bps_at(&schedule, start, start - 7 * DAY), 5_000);
+        assert_eq!(RefundStep::refund_bps_at(&schedule, start, start - 1), 5_000);
+        assert_eq!(RefundStep::refund_bps_at(&schedule, start, start), 0);
+
+        // Deadlines must move later and refunds must not grow
+        let reversed = [schedule[1], schedule[0]];
+        assert!(Event::validate_refund_schedule(&reversed).is_err());
+        let growing = [
+            RefundStep { cutoff: 7 * DAY, refund_bps: 5_000 },
+            RefundStep { cutoff: 0, refund_bps: 10_000 },
+        ];
+        assert!(Event::validate_refund_schedule(&growing).is_err());
+        let too_many = [RefundStep::default(); 5];
+        assert!(Event::validate_refund_schedule(&too_many).is_err());
+    }
 }
 
     #[test]
     fn test_event_size() {
         use crate::state::Event;
-        assert_eq!(Event::SIZE, 455);
+        assert_eq!(Event::SIZE, 594);
         println!("✅ Event size: {} bytes", Event::SIZE);
     }
 
@@ -124,6 +178,12 @@ mod tests {
         // Invalid refund window
         assert!(validate_refund_window(49 * 3600).is_err()); // Too long
         
+        // Check-in offsets from 0 up to a day
+        assert!(validate_checkin_window(0, 0).is_ok());
+        assert!(validate_checkin_window(4 * 3600, 24 * 3600).is_ok());
+        assert!(validate_checkin_window(-1, 3600).is_err());
+        assert!(validate_checkin_window(3600, 25 * 3600).is_err());
+        
         println!("✅ Event validation tests passing");
     }
 
@@ -132,8 +192,8 @@ mod tests {
         use crate::utils::*;
         
         // Test quantity validation
-        assert!(1 <= MAX_BATCH_MINT);
-        assert!(15 <= MAX_BATCH_MINT);
+        const _: () = assert!(1 <= MAX_BATCH_MINT);
+        const _: () = assert!(15 <= MAX_BATCH_MINT);
         
         // Test fee calculations
         let ticket_price = 1_000_000_000; // 1 SOL
@@ -143,6 +203,44 @@ mod tests {
         
         println!("✅ Ticket operation tests passing");
     }
+
+    #[test]
+    fn test_ticket_custody() {
+        use crate::state::Ticket;
+        use anchor_lang::prelude::Pubkey;
+
+        let holder = Pubkey::new_unique();
+        let backend = Pubkey::new_unique();
+        let ticket = Ticket::new(Pubkey::new_unique(), 1, 0, Pubkey::new_unique(), [1u8; 32], holder, Pubkey::new_unique());
+
+        // Custodial: only a custodian, never the holder directly
+        assert!(ticket.can_act(false, &backend, true));
+        assert!(!ticket.can_act(false, &holder, false));
+
+        // Self-custody: only the holder's wallet, whoever the custodian is
+        assert!(ticket.can_act(true, &holder, false));
+        assert!(!ticket.can_act(true, &backend, true));
+
+        let unclaimed = Ticket::new(Pubkey::new_unique(), 2, 0, Pubkey::new_unique(), [2u8; 32], Pubkey::default(), Pubkey::new_unique());
+        assert!(!unclaimed.can_act(true, &Pubkey::default(), false));
+    }
+
+    #[test]
+    fn test_owner_commitment() {
+        use crate::state::Ticket;
+        use anchor_lang::prelude::Pubkey;
+
+        // Same vector as the backend helper: sha256(salt || owner_id)
+        let salt = [0u8; 32];
+        let commitment = Ticket::owner_commitment("user-12345678", &salt);
+        assert_eq!(&commitment[..4], &[80, 19, 215, 186]);
+
+        let ticket = Ticket::new(Pubkey::new_unique(), 1, 0, Pubkey::new_unique(), commitment, Pubkey::default(), Pubkey::new_unique());
+        assert!(ticket.is_owned_by("user-12345678", &salt));
+        assert!(!ticket.is_owned_by("user-87654321", &salt));
+        assert!(!ticket.is_owned_by("user-12345678", &[1u8; 32]));
+        assert_eq!(Ticket::SIZE, 240);
+    }
     
     #[test]
     fn test_compute_limits() {
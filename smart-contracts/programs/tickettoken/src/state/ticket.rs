@@ -1,20 +1,131 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::constants::MAX_GATE_NAME;
+use crate::errors::TicketTokenError;
 
 #[account]
 pub struct Ticket {
     pub event: Pubkey,              // 32 bytes - Parent event PDA
     pub ticket_id: u64,             // 8 bytes - Unique ID within event
+    pub tier_index: u8,             // 1 byte - Tier the ticket was sold in
     pub nft_asset_id: Pubkey,       // 32 bytes - Metaplex cNFT asset ID
-    pub current_owner_id: String,   // 4 + 64 bytes - Backend user ID
+    pub owner_commitment: [u8; 32], // 32 bytes - sha256(salt || backend user ID)
+    pub owner_wallet: Pubkey,       // 32 bytes - Wallet holding the cNFT, a backend wallet if custodial
     pub used: bool,                 // 1 byte - Has been scanned
     pub verified_at: Option<i64>,   // 1 + 8 bytes - When scanned
+    pub verified_gate: [u8; MAX_GATE_NAME], // 16 bytes - Gate/zone that scanned it
     pub transfer_count: u32,        // 4 bytes - Number of resales
+    pub provenance_hash: [u8; 32],  // 32 bytes - Rolling hash over every transfer
+    pub rent_payer: Pubkey,         // 32 bytes - Paid the PDA's rent, gets it back on close
     pub bump: u8,                   // 1 byte - PDA bump
 }
 
 impl Ticket {
-    pub const MAX_OWNER_ID_LEN: usize = 64;
-    pub const SIZE: usize = 8 + 32 + 8 + 32 + (4 + 64) + 1 + 9 + 4 + 1; // ~163 bytes
+    pub const SIZE: usize = 8 + 32 + 8 + 1 + 32 + 32 + 32 + 1 + 9 + MAX_GATE_NAME + 4 + 32 + 32 + 1; // 240 bytes
+
+    /// Fresh, unscanned registry entry for a sold ticket. The bump is
+    /// filled in by whoever creates the PDA.
+    pub fn new(
+        event: Pubkey,
+        ticket_id: u64,
+        tier_index: u8,
+        nft_asset_id: Pubkey,
+        owner_commitment: [u8; 32],
+        owner_wallet: Pubkey,
+        rent_payer: Pubkey,
+    ) -> Self {
+        Ticket {
+            event,
+            ticket_id,
+            tier_index,
+            nft_asset_id,
+            owner_commitment,
+            owner_wallet,
+            used: false,
+            verified_at: None,
+            verified_gate: [0u8; MAX_GATE_NAME],
+            transfer_count: 0,
+            provenance_hash: Self::provenance_genesis(&event, ticket_id),
+            rent_payer,
+            bump: 0,
+        }
+    }
+
+    /// Close the registry entry of a ticket being refunded, so it can no
+    /// longer be scanned once its seat is sold again. `ticket_info` must be
+    /// the ticket's PDA; an empty account means it was never registered.
+    /// Transfers move the cNFT along with the registry entry, so a registered
+    /// ticket is refunded only to its current `owner_wallet`. Returns the
+    /// closed entry for callers with further checks.
+    pub fn close_refunded(
+        ticket_info: &AccountInfo,
+        holder: &Pubkey,
+        rent_payer: Option<&AccountInfo>,
+    ) -> Result<Option<Ticket>> {
+        if ticket_info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*ticket_info.owner, crate::ID, TicketTokenError::InvalidTicket);
+        let ticket = Ticket::try_deserialize(&mut &ticket_info.try_borrow_data()?[..])?;
+        require_keys_eq!(ticket.owner_wallet, *holder, TicketTokenError::NotTicketHolder);
+
+        let rent_payer = rent_payer.ok_or(TicketTokenError::InvalidRentPayer)?;
+        require_keys_eq!(rent_payer.key(), ticket.rent_payer, TicketTokenError::InvalidRentPayer);
+
+        // Hand the rent back and return the account to the system program
+        let rent = ticket_info.lamports();
+        **ticket_info.try_borrow_mut_lamports()? = 0;
+        let payer_lamports = rent_payer.lamports();
+        **rent_payer.try_borrow_mut_lamports()? = payer_lamports
+            .checked_add(rent)
+            .ok_or(TicketTokenError::MathOverflow)?;
+        ticket_info.assign(&anchor_lang::system_program::ID);
+        ticket_info.realloc(0, false)?;
+        Ok(Some(ticket))
+    }
+
+    /// Start of a ticket's provenance chain, tied to the ticket's identity
+    pub fn provenance_genesis(event: &Pubkey, ticket_id: u64) -> [u8; 32] {
+        hashv(&[event.as_ref(), &ticket_id.to_le_bytes()]).to_bytes()
+    }
+
+    /// Next link of the provenance chain for one transfer or resale
+    pub fn provenance_step(
+        previous_hash: &[u8; 32],
+        from_commitment: &[u8; 32],
+        to_commitment: &[u8; 32],
+        sale_price: u64,
+        timestamp: i64,
+    ) -> [u8; 32] {
+        hashv(&[
+            previous_hash,
+            from_commitment,
+            to_commitment,
+            &sale_price.to_le_bytes(),
+            &timestamp.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Commitment to a backend user ID. The salt stays off-chain, so the
+    /// ID can't be recovered or brute-forced from account data or logs.
+    pub fn owner_commitment(owner_id: &str, salt: &[u8; 32]) -> [u8; 32] {
+        hashv(&[salt, owner_id.as_bytes()]).to_bytes()
+    }
+
+    pub fn is_owned_by(&self, owner_id: &str, salt: &[u8; 32]) -> bool {
+        self.owner_commitment == Self::owner_commitment(owner_id, salt)
+    }
+
+    /// Custodial tickets are moved by the backend (a custodian); on
+    /// self-custody events only the holder's own wallet can act
+    pub fn can_act(&self, self_custody: bool, signer: &Pubkey, is_custodian: bool) -> bool {
+        if self_custody {
+            self.owner_wallet != Pubkey::default() && *signer == self.owner_wallet
+        } else {
+            is_custodian
+        }
+    }
 }
 
 // Legacy structs kept for backwards compatibility with existing minting code
@@ -39,10 +150,49 @@ impl TicketMetadata {
     }
 }
 
+/// Identifies a ticket cNFT leaf for instructions that prove, move or
+/// burn it. The ticket fields must match the leaf's URI; root/nonce/index
+/// are the Bubblegum proof arguments, with proof nodes in remaining accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TicketLeafArgs {
+    pub tree_index: u8,
+    pub tier_index: u8,
+    pub ticket_number: u32,
+    pub section: String,
+    pub row: u16,
+    pub seat: u32,
+    pub root: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+/// Royalty creator recorded in a ticket leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LeafCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Parts of a ticket leaf's metadata that were set per ticket at mint.
+/// Everything else is fixed, so the program rebuilds the full metadata
+/// from these to hash the leaf.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TicketLeafMetadata {
+    pub name: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<LeafCreator>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct MintTicketArgs {
+    pub tier_index: u8,
     pub quantity: u8,
     pub section: String,
-    pub row: String,
+    pub row: u16,
     pub seat_start: u32,
+    /// Owner commitment for each Ticket PDA passed in remaining accounts,
+    /// in order; all zeros for a ticket held only by the buyer's wallet
+    pub owner_commitments: Vec<[u8; 32]>,
 }
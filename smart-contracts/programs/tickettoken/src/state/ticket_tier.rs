@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_TIER_NAME;
+use crate::errors::TicketTokenError;
+
+/// A price class within an event (GA, VIP, early bird, ...). Event-wide
+/// capacity still applies on top of each tier's own capacity.
+#[account]
+#[derive(Default)]
+pub struct TicketTier {
+    pub event: Pubkey,                  // 32 bytes - Parent event PDA
+    pub index: u8,                      // 1 byte - Position in the event's tier list
+    pub name: [u8; MAX_TIER_NAME],      // 16 bytes - Display name
+    pub price: u64,                     // 8 bytes - Price in lamports
+    pub capacity: u32,                  // 4 bytes - Tickets in this tier
+    pub sold: u32,                      // 4 bytes - Tickets sold in this tier
+    pub sale_start: i64,                // 8 bytes - 0 = on sale immediately
+    pub sale_end: i64,                  // 8 bytes - 0 = until the event starts
+    pub bump: u8,                       // 1 byte - PDA bump
+}
+
+impl TicketTier {
+    pub const SIZE: usize = 32 + 1 + MAX_TIER_NAME + 8 + 4 + 4 + 8 + 8 + 1;
+
+    pub fn remaining(&self) -> u32 {
+        self.capacity.saturating_sub(self.sold)
+    }
+
+    /// Check the optional sale window
+    pub fn check_sale_window(&self, now: i64) -> Result<()> {
+        require!(
+            self.sale_start == 0 || now >= self.sale_start,
+            TicketTokenError::TierSaleNotStarted
+        );
+        require!(
+            self.sale_end == 0 || now < self.sale_end,
+            TicketTokenError::TierSaleEnded
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTicketTierParams {
+    pub name: String,
+    pub price: u64,
+    pub capacity: u32,
+    pub sale_start: i64,
+    pub sale_end: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(sale_start: i64, sale_end: i64) -> TicketTier {
+        TicketTier {
+            capacity: 10,
+            sold: 4,
+            sale_start,
+            sale_end,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ticket_tier_size() {
+        assert_eq!(TicketTier::SIZE, 82);
+        assert_eq!(tier(0, 0).remaining(), 6);
+    }
+
+    #[test]
+    fn test_sale_window() {
+        // Open-ended on both sides
+        assert!(tier(0, 0).check_sale_window(1_000).is_ok());
+
+        let windowed = tier(100, 200);
+        assert!(windowed.check_sale_window(99).is_err());
+        assert!(windowed.check_sale_window(100).is_ok());
+        assert!(windowed.check_sale_window(199).is_ok());
+        assert!(windowed.check_sale_window(200).is_err());
+    }
+}
@@ -1,4 +1,28 @@
 use anchor_lang::prelude::*;
+use crate::constants::{MAX_TREE_CAPACITY, TREE_MAX_BUFFER_SIZE, TREE_MAX_CANOPY_DEPTH};
+use crate::errors::TicketTokenError;
+
+/// (max_depth, max_buffer_size) pairs accepted by spl-account-compression,
+/// using the largest buffer up to TREE_MAX_BUFFER_SIZE for each depth
+const DEPTH_BUFFER_PAIRS: [(u8, u16); 17] = [
+    (3, 8),
+    (5, 8),
+    (6, 16),
+    (7, 16),
+    (8, 16),
+    (9, 16),
+    (10, 32),
+    (11, 32),
+    (12, 32),
+    (13, 32),
+    (14, 256),
+    (15, 64),
+    (16, 64),
+    (17, 64),
+    (18, 64),
+    (19, 64),
+    (20, 256),
+];
 
 /// Optimal tree configuration based on research
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
@@ -15,10 +39,35 @@ impl TreeConfig {
         Self {
             max_depth: 14,        // 2^14 = 16,384 tickets
             max_buffer_size: 256, // 256 concurrent operations
-            canopy_depth: 17,     // Reduces proof size to ~640 bytes
+            canopy_depth: 10,     // Proofs need 4 nodes (128 bytes)
         }
     }
     
+    /// Smallest valid tree holding `capacity` leaves, with enough canopy
+    /// cached on-chain that proofs fit in `max_proof_bytes`. Larger events
+    /// roll over into more trees.
+    pub fn for_capacity(capacity: u32, max_proof_bytes: u16) -> Result<Self> {
+        require!(
+            capacity > 0 && capacity <= MAX_TREE_CAPACITY,
+            TicketTokenError::InvalidCapacity
+        );
+        
+        let (max_depth, pair_buffer_size) = DEPTH_BUFFER_PAIRS
+            .iter()
+            .copied()
+            .find(|(depth, _)| (1u64 << depth) >= capacity as u64)
+            .ok_or(TicketTokenError::InvalidCapacity)?;
+        
+        let proof_nodes = (max_proof_bytes / 32).min(max_depth as u16) as u8;
+        let config = Self {
+            max_depth,
+            max_buffer_size: pair_buffer_size.min(TREE_MAX_BUFFER_SIZE as u16),
+            canopy_depth: max_depth - proof_nodes,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+    
     /// Calculate tree capacity
     pub fn capacity(&self) -> u32 {
         1u32 << self.max_depth
@@ -35,27 +84,31 @@ impl TreeConfig {
             crate::errors::TicketTokenError::InvalidBufferSize
         );
         require!(
-            self.canopy_depth <= self.max_depth,
+            self.canopy_depth <= self.max_depth && self.canopy_depth <= TREE_MAX_CANOPY_DEPTH,
             crate::errors::TicketTokenError::InvalidCanopyDepth
         );
         Ok(())
     }
     
-    /// Calculate tree account size
+    /// Calculate tree account size (header + tree + canopy), matching
+    /// spl-account-compression's `getConcurrentMerkleTreeAccountSize`
     pub fn account_size(&self) -> usize {
         use spl_account_compression::state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1;
         
-        let merkle_tree_size = (2 << (self.max_depth - 1)) * 32;
-        let buffer_size = (self.max_buffer_size as usize).saturating_mul(256);
+        let depth = self.max_depth as usize;
+        let change_log_size = 32 + 32 * depth + 4 + 4;   // root + path + index + padding
+        let rightmost_path_size = 32 * depth + 32 + 4 + 4; // proof + leaf + index + padding
+        let merkle_tree_size = (8 + 8 + 8)                 // sequence number, active index, buffer size
+            + (self.max_buffer_size as usize).saturating_mul(change_log_size)
+            + rightmost_path_size;
         let canopy_size = if self.canopy_depth > 0 {
-            (2 << self.canopy_depth) * 32
+            ((1usize << (self.canopy_depth + 1)) - 2) * 32
         } else {
             0
         };
         
         CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1
             .saturating_add(merkle_tree_size)
-            .saturating_add(buffer_size)
             .saturating_add(canopy_size)
     }
     
@@ -75,8 +128,36 @@ mod tests {
         let config = TreeConfig::optimal();
         assert_eq!(config.max_depth, 14);
         assert_eq!(config.max_buffer_size, 256);
-        assert_eq!(config.canopy_depth, 17);
+        assert_eq!(config.canopy_depth, 10);
         assert_eq!(config.capacity(), 16_384);
+        assert!(config.validate().is_ok());
+    }
+    
+    #[test]
+    fn test_for_capacity() {
+        // Club show: smallest tree that fits, no canopy needed for short proofs
+        let club = TreeConfig::for_capacity(500, 1024).unwrap();
+        assert_eq!(club.max_depth, 9);
+        assert_eq!(club.canopy_depth, 0);
+        assert!(club.capacity() >= 500);
+        
+        // Arena: depth 14, canopy sized so proofs fit in 4 nodes
+        let arena = TreeConfig::for_capacity(16_384, 128).unwrap();
+        assert_eq!(arena.max_depth, 14);
+        assert_eq!(arena.max_buffer_size, 256);
+        assert_eq!(arena.canopy_depth, 10);
+        
+        // Largest single tree: just over one depth step
+        let stadium = TreeConfig::for_capacity(40_000, 320).unwrap();
+        assert_eq!(stadium.max_depth, 16);
+        assert_eq!(stadium.canopy_depth, 6);
+        assert_eq!(TreeConfig::for_capacity(MAX_TREE_CAPACITY, 320).unwrap().max_depth, 16);
+        
+        // Out of range; stadium events spill onto more trees instead
+        assert!(TreeConfig::for_capacity(0, 1024).is_err());
+        assert!(TreeConfig::for_capacity(MAX_TREE_CAPACITY + 1, 1024).is_err());
+        assert!(TreeConfig::for_capacity(80_000, 320).is_err());
+        assert!(TreeConfig::for_capacity(1_000_000, 96).is_err());
     }
     
     #[test]
@@ -110,4 +191,14 @@ mod tests {
         // The optimal config has valid parameters, even if validate() fails due to Rent::get()
         // We've tested the actual validation logic above
     }
+    
+    #[test]
+    fn test_account_size_matches_account_compression() {
+        // Reference values from @solana/spl-account-compression
+        let small = TreeConfig { max_depth: 14, max_buffer_size: 64, canopy_depth: 0 };
+        assert_eq!(small.account_size(), 31_800);
+        
+        let with_canopy = TreeConfig { max_depth: 14, max_buffer_size: 64, canopy_depth: 10 };
+        assert_eq!(with_canopy.account_size(), 31_800 + 2046 * 32);
+    }
 }
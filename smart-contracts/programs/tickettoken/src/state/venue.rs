@@ -1,4 +1,18 @@
 use anchor_lang::prelude::*;
+use crate::constants::MAX_PAYOUT_WALLETS;
+use crate::errors::TicketTokenError;
+use crate::utils::calculate_fee;
+
+/// A wallet that receives `weight_bps` of everything paid to the venue
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct PayoutWallet {
+    pub wallet: Pubkey,
+    pub weight_bps: u16,
+}
+
+impl PayoutWallet {
+    pub const SIZE: usize = 32 + 2;
+}
 
 #[account]
 pub struct Venue {
@@ -11,14 +25,64 @@ pub struct Venue {
     pub event_count: u64,           // 8 bytes
     pub total_sales: u64,           // 8 bytes
     pub bump: u8,                   // 1 byte
+    pub revocation_reason: u16,     // 2 bytes - Last revocation code, 0 if none
+    pub payout_count: u8,           // 1 byte - Used entries in payout_wallets
+    pub payout_wallets: [PayoutWallet; MAX_PAYOUT_WALLETS], // 102 bytes
+    pub deactivated_by: Pubkey,     // 32 bytes - Who last set active = false, default while active
 }
 
 impl Venue {
-    pub const SIZE: usize = 32 + 32 + 64 + 64 + 1 + 1 + 8 + 8 + 1;
+    pub const SIZE: usize = 32 + 32 + 64 + 64 + 1 + 1 + 8 + 8 + 1 + 2 + 1 + PayoutWallet::SIZE * MAX_PAYOUT_WALLETS + 32;
 
     pub fn is_active(&self) -> bool {
         self.active && self.verified
     }
+
+    /// Deactivated by someone other than the owner, i.e. a platform admin.
+    /// Only an admin can lift such a suspension.
+    pub fn is_suspended(&self) -> bool {
+        !self.active && self.deactivated_by != Pubkey::default() && self.deactivated_by != self.owner
+    }
+
+    /// Where venue proceeds go. Until set_venue_payout is called
+    /// everything goes to the owner.
+    pub fn payout_wallets(&self) -> Vec<PayoutWallet> {
+        if self.payout_count == 0 {
+            return vec![PayoutWallet { wallet: self.owner, weight_bps: 10_000 }];
+        }
+        self.payout_wallets[..self.payout_count as usize].to_vec()
+    }
+
+    pub fn validate_payout_wallets(wallets: &[PayoutWallet]) -> Result<()> {
+        require!(
+            !wallets.is_empty() && wallets.len() <= MAX_PAYOUT_WALLETS,
+            TicketTokenError::InvalidPayoutWallets
+        );
+        let mut total: u32 = 0;
+        for (i, payout) in wallets.iter().enumerate() {
+            require!(
+                payout.wallet != Pubkey::default()
+                    && payout.weight_bps > 0
+                    && wallets[..i].iter().all(|other| other.wallet != payout.wallet),
+                TicketTokenError::InvalidPayoutWallets
+            );
+            total += payout.weight_bps as u32;
+        }
+        require!(total == 10_000, TicketTokenError::InvalidPayoutWallets);
+        Ok(())
+    }
+
+    /// Divide `amount` by weight; rounding dust goes to the first wallet
+    pub fn split_payout(&self, amount: u64) -> Result<Vec<(Pubkey, u64)>> {
+        let wallets = self.payout_wallets();
+        let mut shares = wallets
+            .iter()
+            .map(|payout| Ok((payout.wallet, calculate_fee(amount, payout.weight_bps)?)))
+            .collect::<Result<Vec<(Pubkey, u64)>>>()?;
+        let allocated: u64 = shares.iter().map(|(_, share)| share).sum();
+        shares[0].1 += amount - allocated;
+        Ok(shares)
+    }
 }
 
 impl Default for Venue {
@@ -33,6 +97,66 @@ impl Default for Venue {
             event_count: 0,
             total_sales: 0,
             bump: 0,
+            revocation_reason: 0,
+            payout_count: 0,
+            payout_wallets: [PayoutWallet::default(); MAX_PAYOUT_WALLETS],
+            deactivated_by: Pubkey::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payout(wallet: Pubkey, weight_bps: u16) -> PayoutWallet {
+        PayoutWallet { wallet, weight_bps }
+    }
+
+    #[test]
+    fn test_payout_defaults_to_owner() {
+        let venue = Venue { owner: Pubkey::new_unique(), ..Default::default() };
+        assert_eq!(venue.payout_wallets(), vec![payout(venue.owner, 10_000)]);
+        assert_eq!(venue.split_payout(1_000).unwrap(), vec![(venue.owner, 1_000)]);
+    }
+
+    #[test]
+    fn test_weighted_payout_split() {
+        let (venue_wallet, promoter, landlord) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let venue = Venue {
+            payout_count: 3,
+            payout_wallets: [payout(venue_wallet, 5_000), payout(promoter, 3_333), payout(landlord, 1_667)],
+            ..Default::default()
+        };
+
+        let shares = venue.split_payout(1_000_001).unwrap();
+        assert_eq!(shares, vec![(venue_wallet, 500_001), (promoter, 333_300), (landlord, 166_700)]);
+        assert_eq!(shares.iter().map(|(_, share)| share).sum::<u64>(), 1_000_001);
+    }
+
+    #[test]
+    fn test_payout_wallet_validation() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(Venue::validate_payout_wallets(&[payout(a, 10_000)]).is_ok());
+        assert!(Venue::validate_payout_wallets(&[payout(a, 7_000), payout(b, 3_000)]).is_ok());
+        assert!(Venue::validate_payout_wallets(&[]).is_err());
+        assert!(Venue::validate_payout_wallets(&[payout(a, 9_999)]).is_err());
+        assert!(Venue::validate_payout_wallets(&[payout(a, 5_000), payout(a, 5_000)]).is_err());
+        assert!(Venue::validate_payout_wallets(&[payout(a, 10_000), payout(b, 0)]).is_err());
+        assert!(Venue::validate_payout_wallets(&[payout(Pubkey::default(), 10_000)]).is_err());
+        let too_many = [payout(a, 2_500), payout(b, 2_500), payout(Pubkey::new_unique(), 2_500), payout(Pubkey::new_unique(), 2_500)];
+        assert!(Venue::validate_payout_wallets(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_admin_suspension() {
+        let owner = Pubkey::new_unique();
+        let closed = Venue { owner, deactivated_by: owner, ..Default::default() };
+        assert!(!closed.is_suspended());
+
+        let suspended = Venue { owner, deactivated_by: Pubkey::new_unique(), ..Default::default() };
+        assert!(suspended.is_suspended());
+        assert!(!Venue { active: true, deactivated_by: Pubkey::default(), ..suspended }.is_suspended());
+    }
+}
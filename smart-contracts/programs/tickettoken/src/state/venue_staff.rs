@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::errors::TicketTokenError;
+use crate::state::Venue;
+
+/// Delegated key acting for a venue, one PDA per (venue, staff key).
+/// `permissions` is a bitmask of the flags below.
+#[account]
+pub struct VenueStaff {
+    pub venue: Pubkey,              // 32 bytes - Venue the key works for
+    pub staff: Pubkey,              // 32 bytes - Delegated signer
+    pub permissions: u8,            // 1 byte - Permission flags
+    pub expires_at: i64,            // 8 bytes - Unix timestamp, 0 = no expiry
+    pub added_at: i64,              // 8 bytes - Unix timestamp
+    pub bump: u8,                   // 1 byte - PDA bump
+}
+
+impl VenueStaff {
+    pub const SIZE: usize = 32 + 32 + 1 + 8 + 8 + 1;
+
+    pub const CREATE_EVENTS: u8 = 1 << 0;
+    pub const EDIT_EVENTS: u8 = 1 << 1;
+    pub const SCAN_TICKETS: u8 = 1 << 2;
+    pub const ISSUE_COMPS: u8 = 1 << 3;
+    pub const TRANSFER_TICKETS: u8 = 1 << 4;
+    pub const ALL_PERMISSIONS: u8 = Self::CREATE_EVENTS
+        | Self::EDIT_EVENTS
+        | Self::SCAN_TICKETS
+        | Self::ISSUE_COMPS
+        | Self::TRANSFER_TICKETS;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    pub fn allows(&self, permission: u8, now: i64) -> bool {
+        self.permissions & permission == permission && !self.is_expired(now)
+    }
+
+    /// The venue owner can do anything; anyone else needs a live staff
+    /// record for this venue carrying `permission`
+    pub fn is_authorized(
+        venue: &Pubkey,
+        venue_owner: &Pubkey,
+        authority: &Pubkey,
+        staff: Option<&VenueStaff>,
+        permission: u8,
+        now: i64,
+    ) -> bool {
+        *authority == *venue_owner
+            || staff.is_some_and(|staff| {
+                staff.venue == *venue && staff.staff == *authority && staff.allows(permission, now)
+            })
+    }
+
+    pub fn authorize(
+        venue: &Account<Venue>,
+        authority: &Pubkey,
+        staff: Option<&VenueStaff>,
+        permission: u8,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            Self::is_authorized(&venue.key(), &venue.owner, authority, staff, permission, now),
+            TicketTokenError::UnauthorizedVenue
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staff(permissions: u8, expires_at: i64) -> VenueStaff {
+        VenueStaff {
+            venue: Pubkey::new_unique(),
+            staff: Pubkey::new_unique(),
+            permissions,
+            expires_at,
+            added_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_staff_permissions() {
+        let scanner = staff(VenueStaff::SCAN_TICKETS, 0);
+        assert!(scanner.allows(VenueStaff::SCAN_TICKETS, 1_000));
+        assert!(!scanner.allows(VenueStaff::CREATE_EVENTS, 1_000));
+        assert!(!scanner.allows(VenueStaff::SCAN_TICKETS | VenueStaff::EDIT_EVENTS, 1_000));
+
+        let manager = staff(VenueStaff::CREATE_EVENTS | VenueStaff::EDIT_EVENTS, 0);
+        assert!(manager.allows(VenueStaff::EDIT_EVENTS, 1_000));
+        assert!(!manager.allows(VenueStaff::ISSUE_COMPS, 1_000));
+    }
+
+    #[test]
+    fn test_staff_expiry() {
+        let temp = staff(VenueStaff::ALL_PERMISSIONS, 2_000);
+        assert!(temp.allows(VenueStaff::SCAN_TICKETS, 1_999));
+        assert!(!temp.allows(VenueStaff::SCAN_TICKETS, 2_000));
+        assert!(!staff(VenueStaff::SCAN_TICKETS, 0).is_expired(i64::MAX));
+    }
+
+    #[test]
+    fn test_staff_authorization() {
+        let owner = Pubkey::new_unique();
+        let scanner = staff(VenueStaff::SCAN_TICKETS, 0);
+        let venue = scanner.venue;
+        let scan = VenueStaff::SCAN_TICKETS;
+
+        assert!(VenueStaff::is_authorized(&venue, &owner, &owner, None, scan, 0));
+        assert!(VenueStaff::is_authorized(&venue, &owner, &scanner.staff, Some(&scanner), scan, 0));
+        // No record, someone else's record, or a record for another venue
+        assert!(!VenueStaff::is_authorized(&venue, &owner, &scanner.staff, None, scan, 0));
+        assert!(!VenueStaff::is_authorized(&venue, &owner, &Pubkey::new_unique(), Some(&scanner), scan, 0));
+        assert!(!VenueStaff::is_authorized(&Pubkey::new_unique(), &owner, &scanner.staff, Some(&scanner), scan, 0));
+    }
+}
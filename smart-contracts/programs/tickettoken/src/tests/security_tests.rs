@@ -3,7 +3,6 @@ mod tests {
     use crate::utils::reentrancy::ReentrancyGuard;
     use crate::utils::{safe_add, safe_mul, safe_div, calculate_fee};
     use crate::constants::*;
-    use anchor_lang::prelude::*;
 
     #[test]
     fn test_reentrancy_guard_initialization() {
@@ -1,28 +1,89 @@
 use anchor_lang::prelude::*;
-use mpl_bubblegum::types::{MetadataArgs, TokenStandard, Collection, Creator, Uses, UseMethod, TokenProgramVersion};
-use crate::state::Event;
+use mpl_bubblegum::hash::{hash_creators, hash_metadata};
+use mpl_bubblegum::instructions::{BurnCpiBuilder, MintToCollectionV1CpiBuilder, TransferCpiBuilder, VerifyLeafCpiBuilder};
+use mpl_bubblegum::types::{Creator, LeafSchema, MetadataArgs, TokenStandard, Collection, Uses, UseMethod, TokenProgramVersion};
+use crate::state::{Event, TicketTier, TicketLeafArgs, TicketLeafMetadata};
+use crate::errors::TicketTokenError;
+use crate::constants::{CNFT_MAX_NAME_LENGTH, CNFT_MAX_URI_LENGTH, CNFT_SYMBOL};
+use crate::utils::bytes_to_string;
+use crate::utils::royalties::Royalties;
 
 /// Create ticket metadata for compressed NFT
 pub fn create_ticket_metadata(
     event: &Event,
+    tier: &TicketTier,
     ticket_number: u32,
-    _section: &str,
-    _row: &str,
-    _seat: &str,
-    platform_treasury: Pubkey,
+    section: &str,
+    row: u16,
+    seat: u32,
+    royalties: &Royalties,
+) -> MetadataArgs {
+    let mut name = format!(
+        "{} #{} - {}",
+        bytes_to_string(&tier.name),
+        ticket_number,
+        bytes_to_string(&event.name),
+    );
+    truncate_to(&mut name, CNFT_MAX_NAME_LENGTH);
+
+    // Bubblegum flips the collection to verified when minting into it
+    ticket_metadata_args(
+        event,
+        name,
+        ticket_uri(event, tier.index, ticket_number, section, row, seat),
+        royalties.seller_fee_basis_points,
+        royalties.creators.clone(),
+        false,
+    )
+}
+
+/// Full metadata of a minted ticket leaf, rebuilt from its per-ticket
+/// parts once they are checked against the ticket the leaf claims to be
+pub fn ticket_leaf_metadata(
+    event: &Event,
+    metadata: &TicketLeafMetadata,
+    leaf: &TicketLeafArgs,
+) -> Result<MetadataArgs> {
+    validate_ticket_metadata(event, metadata, leaf)?;
+    Ok(ticket_metadata_args(
+        event,
+        metadata.name.clone(),
+        metadata.uri.clone(),
+        metadata.seller_fee_basis_points,
+        metadata
+            .creators
+            .iter()
+            .map(|creator| Creator {
+                address: creator.address,
+                verified: creator.verified,
+                share: creator.share,
+            })
+            .collect(),
+        true,
+    ))
+}
+
+/// Ticket metadata fields that are the same for every ticket
+fn ticket_metadata_args(
+    event: &Event,
+    name: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Vec<Creator>,
+    collection_verified: bool,
 ) -> MetadataArgs {
     MetadataArgs {
-        name: format!("Ticket #{} - {}", ticket_number, std::str::from_utf8(&event.name).unwrap_or("Event")),
-        symbol: "TKT".to_string(),
-        uri: format!("{}/tickets/{}", std::str::from_utf8(&event.metadata_uri).unwrap_or(""), ticket_number),
-        seller_fee_basis_points: 1000, // 10% royalty on resales
+        name,
+        symbol: CNFT_SYMBOL.to_string(),
+        uri,
+        seller_fee_basis_points,
         primary_sale_happened: true,
         is_mutable: false,
         edition_nonce: None,
         token_standard: Some(TokenStandard::NonFungible),
         collection: Some(Collection {
-            verified: false,
-            key: event.venue,
+            verified: collection_verified,
+            key: event.collection_mint,
         }),
         uses: Some(Uses {
             use_method: UseMethod::Single,
@@ -30,21 +91,223 @@ pub fn create_ticket_metadata(
             total: 1,
         }),
         token_program_version: TokenProgramVersion::Original,
-        creators: vec![
-            Creator {
-                address: event.venue,
-                verified: false,
-                share: 50,  // 50% to venue
-            },
-            Creator {
-                address: platform_treasury,
-                verified: false,
-                share: 50,  // 50% to platform
-            }
-        ],
+        creators,
     }
 }
 
+/// Ticket URI. cNFTs carry no attributes, so the tier and seat travel in
+/// the URI, which also lets refunds recover them from a leaf's metadata.
+pub fn ticket_uri(
+    event: &Event,
+    tier_index: u8,
+    ticket_number: u32,
+    section: &str,
+    row: u16,
+    seat: u32,
+) -> String {
+    let mut uri = format!(
+        "{}/tickets/{}?tier={}&section={}&row={}&seat={}",
+        bytes_to_string(&event.metadata_uri),
+        ticket_number,
+        tier_index,
+        section,
+        row,
+        seat,
+    );
+    truncate_to(&mut uri, CNFT_MAX_URI_LENGTH);
+    uri
+}
+
+/// Accounts Bubblegum needs to mint a ticket leaf into one tree
+/// and verify it against the event collection
+pub struct TicketMintAccounts<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub tree_creator: &'a AccountInfo<'info>,
+    pub collection_authority: &'a AccountInfo<'info>,
+    pub collection_mint: &'a AccountInfo<'info>,
+    pub collection_metadata: &'a AccountInfo<'info>,
+    pub collection_edition: &'a AccountInfo<'info>,
+    pub bubblegum_signer: &'a AccountInfo<'info>,
+    pub token_metadata_program: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Mint a verified compressed ticket, signing as the event's tree creator
+/// and collection authority PDAs
+pub fn mint_ticket(
+    accounts: &TicketMintAccounts,
+    metadata: MetadataArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    MintToCollectionV1CpiBuilder::new(accounts.bubblegum_program)
+        .tree_config(accounts.tree_config)
+        .leaf_owner(accounts.leaf_owner)
+        .leaf_delegate(accounts.leaf_owner)
+        .merkle_tree(accounts.merkle_tree)
+        .payer(accounts.payer)
+        .tree_creator_or_delegate(accounts.tree_creator)
+        .collection_authority(accounts.collection_authority)
+        .collection_authority_record_pda(None)
+        .collection_mint(accounts.collection_mint)
+        .collection_metadata(accounts.collection_metadata)
+        .collection_edition(accounts.collection_edition)
+        .bubblegum_signer(accounts.bubblegum_signer)
+        .token_metadata_program(accounts.token_metadata_program)
+        .log_wrapper(accounts.log_wrapper)
+        .compression_program(accounts.compression_program)
+        .system_program(accounts.system_program)
+        .metadata(metadata)
+        .invoke_signed(signer_seeds)?;
+    Ok(())
+}
+
+/// Accounts Bubblegum needs to burn a ticket leaf held by `leaf_owner`
+pub struct TicketBurnAccounts<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Burn a ticket leaf. Bubblegum only accepts the burn if `metadata`
+/// hashes to the leaf at `nonce`/`index`, so a successful burn proves
+/// the holder owned a ticket with exactly this metadata.
+pub fn burn_ticket<'info>(
+    accounts: &TicketBurnAccounts<'_, 'info>,
+    metadata: &MetadataArgs,
+    leaf: &TicketLeafArgs,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    let data_hash = hash_metadata(metadata).map_err(|_| TicketTokenError::InvalidTicketMetadata)?;
+    let creator_hash = hash_creators(&metadata.creators);
+    let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> = proof
+        .iter()
+        .map(|node| (node, false, false))
+        .collect();
+
+    BurnCpiBuilder::new(accounts.bubblegum_program)
+        .tree_config(accounts.tree_config)
+        .leaf_owner(accounts.leaf_owner, true)
+        .leaf_delegate(accounts.leaf_owner, false)
+        .merkle_tree(accounts.merkle_tree)
+        .log_wrapper(accounts.log_wrapper)
+        .compression_program(accounts.compression_program)
+        .system_program(accounts.system_program)
+        .root(leaf.root)
+        .data_hash(data_hash)
+        .creator_hash(creator_hash)
+        .nonce(leaf.nonce)
+        .index(leaf.index)
+        .add_remaining_accounts(&proof_accounts)
+        .invoke()?;
+    Ok(())
+}
+
+/// Accounts Bubblegum needs to move a ticket leaf from `leaf_owner`
+/// to `new_leaf_owner`
+pub struct TicketTransferAccounts<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub new_leaf_owner: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Transfer a ticket leaf, signed by its current owner. Bubblegum makes
+/// the new owner the delegate too, as at mint.
+pub fn transfer_ticket_leaf<'info>(
+    accounts: &TicketTransferAccounts<'_, 'info>,
+    metadata: &MetadataArgs,
+    leaf: &TicketLeafArgs,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    let data_hash = hash_metadata(metadata).map_err(|_| TicketTokenError::InvalidTicketMetadata)?;
+    let creator_hash = hash_creators(&metadata.creators);
+    let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> = proof
+        .iter()
+        .map(|node| (node, false, false))
+        .collect();
+
+    TransferCpiBuilder::new(accounts.bubblegum_program)
+        .tree_config(accounts.tree_config)
+        .leaf_owner(accounts.leaf_owner, true)
+        .leaf_delegate(accounts.leaf_owner, false)
+        .new_leaf_owner(accounts.new_leaf_owner)
+        .merkle_tree(accounts.merkle_tree)
+        .log_wrapper(accounts.log_wrapper)
+        .compression_program(accounts.compression_program)
+        .system_program(accounts.system_program)
+        .root(leaf.root)
+        .data_hash(data_hash)
+        .creator_hash(creator_hash)
+        .nonce(leaf.nonce)
+        .index(leaf.index)
+        .add_remaining_accounts(&proof_accounts)
+        .invoke()?;
+    Ok(())
+}
+
+/// Check that `leaf_owner` holds a ticket leaf with exactly this metadata
+/// at `nonce`/`index`, without touching the tree. Tickets are minted with
+/// the owner as delegate, and Bubblegum transfers keep it that way.
+pub fn verify_ticket_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    leaf_owner: &Pubkey,
+    metadata: &MetadataArgs,
+    leaf: &TicketLeafArgs,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    let leaf_hash = LeafSchema::V1 {
+        id: get_asset_id(merkle_tree.key, leaf.nonce),
+        owner: *leaf_owner,
+        delegate: *leaf_owner,
+        nonce: leaf.nonce,
+        data_hash: hash_metadata(metadata).map_err(|_| TicketTokenError::InvalidTicketMetadata)?,
+        creator_hash: hash_creators(&metadata.creators),
+    }.hash();
+    let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> = proof
+        .iter()
+        .map(|node| (node, false, false))
+        .collect();
+
+    VerifyLeafCpiBuilder::new(compression_program)
+        .merkle_tree(merkle_tree)
+        .root(leaf.root)
+        .leaf(leaf_hash)
+        .index(leaf.index)
+        .add_remaining_accounts(&proof_accounts)
+        .invoke()?;
+    Ok(())
+}
+
+/// Check that leaf metadata supplied by a holder describes the ticket
+/// they claim: the URI this program minted for it
+fn validate_ticket_metadata(
+    event: &Event,
+    metadata: &TicketLeafMetadata,
+    leaf: &TicketLeafArgs,
+) -> Result<()> {
+    let expected_uri = ticket_uri(event, leaf.tier_index, leaf.ticket_number, &leaf.section, leaf.row, leaf.seat);
+    require!(
+        metadata.uri == expected_uri,
+        TicketTokenError::InvalidTicketMetadata
+    );
+    Ok(())
+}
+
 /// Derive asset ID for a compressed NFT
 pub fn get_asset_id(tree: &Pubkey, nonce: u64) -> Pubkey {
     Pubkey::find_program_address(
@@ -56,3 +319,127 @@ pub fn get_asset_id(tree: &Pubkey, nonce: u64) -> Pubkey {
         &mpl_bubblegum::ID,
     ).0
 }
+
+/// Bubblegum rejects names/URIs over its limits, so clip on a char boundary
+fn truncate_to(value: &mut String, max_len: usize) {
+    if value.len() > max_len {
+        let mut end = max_len;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::royalties::calculate_royalties;
+    use crate::state::{LeafCreator, PayoutWallet};
+
+    fn sample_event(name: &str, metadata_uri: &str) -> Event {
+        let mut event_name = [0u8; 32];
+        event_name[..name.len()].copy_from_slice(name.as_bytes());
+        let mut uri = [0u8; 64];
+        uri[..metadata_uri.len()].copy_from_slice(metadata_uri.as_bytes());
+
+        Event {
+            name: event_name,
+            metadata_uri: uri,
+            artist_wallet: Pubkey::new_unique(),
+            artist_percentage: 500,
+            venue_percentage: 250,
+            collection_mint: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    fn sample_tier(name: &str) -> TicketTier {
+        let mut tier_name = [0u8; 16];
+        tier_name[..name.len()].copy_from_slice(name.as_bytes());
+
+        TicketTier {
+            index: 1,
+            name: tier_name,
+            ..Default::default()
+        }
+    }
+
+    fn sample_royalties(event: &Event) -> Royalties {
+        calculate_royalties(
+            event.artist_wallet,
+            event.artist_percentage,
+            &[PayoutWallet { wallet: Pubkey::new_unique(), weight_bps: 10_000 }],
+            event.venue_percentage,
+            Pubkey::new_unique(),
+            250,
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_ticket_metadata_fits_bubblegum_limits() {
+        let event = sample_event("A Very Long Festival Name 2026!!", "https://example.com/e/1");
+        let metadata = create_ticket_metadata(&event, &sample_tier("VIP"), 12_345, "A", 1, 1, &sample_royalties(&event));
+
+        assert!(metadata.name.len() <= CNFT_MAX_NAME_LENGTH);
+        assert!(!metadata.name.contains('\0'));
+        assert!(metadata.name.starts_with("VIP #12345 - "));
+        assert_eq!(metadata.uri, "https://example.com/e/1/tickets/12345?tier=1&section=A&row=1&seat=1");
+    }
+
+    #[test]
+    fn test_ticket_metadata_points_at_event_collection() {
+        let event = sample_event("Test Event", "https://example.com/e/1");
+        let metadata = create_ticket_metadata(&event, &sample_tier("GA"), 1, "A", 1, 1, &sample_royalties(&event));
+
+        let collection = metadata.collection.expect("tickets belong to a collection");
+        assert_eq!(collection.key, event.collection_mint);
+        assert!(!collection.verified);
+    }
+
+    #[test]
+    fn test_ticket_leaf_metadata_rebuilds_minted_leaf() {
+        let event = sample_event("Test Event", "https://example.com/e/1");
+        let tier = sample_tier("VIP");
+        let mut minted = create_ticket_metadata(&event, &tier, 7, "A", 3, 12, &sample_royalties(&event));
+        let leaf = TicketLeafArgs {
+            tree_index: 0,
+            tier_index: tier.index,
+            ticket_number: 7,
+            section: "A".to_string(),
+            row: 3,
+            seat: 12,
+            root: [0u8; 32],
+            nonce: 7,
+            index: 7,
+        };
+        let parts = TicketLeafMetadata {
+            name: minted.name.clone(),
+            uri: minted.uri.clone(),
+            seller_fee_basis_points: minted.seller_fee_basis_points,
+            creators: minted
+                .creators
+                .iter()
+                .map(|c| LeafCreator { address: c.address, verified: c.verified, share: c.share })
+                .collect(),
+        };
+
+        // Minted leaves carry a verified collection
+        minted.collection = Some(Collection { verified: true, key: event.collection_mint });
+        assert_eq!(ticket_leaf_metadata(&event, &parts, &leaf).unwrap(), minted);
+
+        // Claiming a pricier tier than the leaf was minted in fails
+        let other_tier = TicketLeafArgs { tier_index: 0, ..leaf.clone() };
+        assert!(ticket_leaf_metadata(&event, &parts, &other_tier).is_err());
+    }
+
+    #[test]
+    fn test_ticket_metadata_uses_event_royalties() {
+        let event = sample_event("Test Event", "https://example.com/e/1");
+        let metadata = create_ticket_metadata(&event, &sample_tier("GA"), 1, "A", 1, 1, &sample_royalties(&event));
+
+        assert_eq!(metadata.seller_fee_basis_points, 1000);
+        assert_eq!(metadata.creators[0].address, event.artist_wallet);
+        assert_eq!(metadata.creators.iter().map(|c| c.share as u32).sum::<u32>(), 100);
+    }
+}
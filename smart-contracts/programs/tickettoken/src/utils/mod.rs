@@ -1,5 +1,7 @@
 pub mod validation;
 pub mod merkle;
+pub mod royalties;
+pub mod provenance;
 
 use anchor_lang::prelude::*;
 use crate::errors::TicketTokenError;
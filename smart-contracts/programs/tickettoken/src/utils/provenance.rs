@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::errors::TicketTokenError;
+use crate::instructions::TicketTransferred;
+use crate::state::Ticket;
+
+/// Replay a ticket's TicketTransferred events, oldest first, and return
+/// the provenance hash they lead to. Fails if the events are out of
+/// order, skip a transfer, don't chain owners, or disagree with the hash
+/// they report.
+pub fn replay_provenance(
+    event: &Pubkey,
+    ticket_id: u64,
+    transfers: &[TicketTransferred],
+) -> Result<[u8; 32]> {
+    let mut hash = Ticket::provenance_genesis(event, ticket_id);
+    let mut owner: Option<[u8; 32]> = None;
+
+    for (i, transfer) in transfers.iter().enumerate() {
+        require!(
+            transfer.event == *event
+                && transfer.ticket_id == ticket_id
+                && transfer.transfer_count as usize == i + 1
+                && owner.map_or(true, |owner| owner == transfer.previous_owner_commitment),
+            TicketTokenError::InvalidProvenance
+        );
+        hash = Ticket::provenance_step(
+            &hash,
+            &transfer.previous_owner_commitment,
+            &transfer.owner_commitment,
+            transfer.sale_price,
+            transfer.timestamp,
+        );
+        require!(hash == transfer.provenance_hash, TicketTokenError::InvalidProvenance);
+        owner = Some(transfer.owner_commitment);
+    }
+
+    Ok(hash)
+}
+
+/// Check that `transfers` is the complete custody history of `ticket`
+pub fn verify_provenance(ticket: &Ticket, transfers: &[TicketTransferred]) -> Result<()> {
+    require!(
+        transfers.len() == ticket.transfer_count as usize,
+        TicketTokenError::InvalidProvenance
+    );
+    let hash = replay_provenance(&ticket.event, ticket.ticket_id, transfers)?;
+    require!(hash == ticket.provenance_hash, TicketTokenError::InvalidProvenance);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply a transfer the way transfer_ticket does and return its event
+    fn transfer(ticket: &mut Ticket, to: [u8; 32], sale_price: u64, timestamp: i64) -> TicketTransferred {
+        let from = ticket.owner_commitment;
+        ticket.provenance_hash = Ticket::provenance_step(&ticket.provenance_hash, &from, &to, sale_price, timestamp);
+        ticket.owner_commitment = to;
+        ticket.transfer_count += 1;
+        TicketTransferred {
+            event: ticket.event,
+            ticket: Pubkey::new_unique(),
+            ticket_id: ticket.ticket_id,
+            from_wallet: Pubkey::default(),
+            to_wallet: Pubkey::default(),
+            previous_owner_commitment: from,
+            owner_commitment: to,
+            sale_price,
+            transfer_count: ticket.transfer_count,
+            provenance_hash: ticket.provenance_hash,
+            authority: Pubkey::default(),
+            timestamp,
+        }
+    }
+
+    fn ticket_with_history() -> (Ticket, Vec<TicketTransferred>) {
+        let mut ticket = Ticket::new(Pubkey::new_from_array([5u8; 32]), 7, 0, Pubkey::new_unique(), [1u8; 32], Pubkey::default(), Pubkey::new_unique());
+        let history = vec![
+            transfer(&mut ticket, [2u8; 32], 0, 1_000),
+            transfer(&mut ticket, [3u8; 32], 1_500_000_000, 2_000),
+            transfer(&mut ticket, [4u8; 32], 0, 3_000),
+        ];
+        (ticket, history)
+    }
+
+    #[test]
+    fn test_verify_full_history() {
+        let (ticket, history) = ticket_with_history();
+        assert!(verify_provenance(&ticket, &history).is_ok());
+
+        let fresh = Ticket::new(Pubkey::new_unique(), 1, 0, Pubkey::new_unique(), [1u8; 32], Pubkey::default(), Pubkey::new_unique());
+        assert!(verify_provenance(&fresh, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_reject_tampered_history() {
+        let (ticket, history) = ticket_with_history();
+
+        // Missing the latest transfer
+        assert!(verify_provenance(&ticket, &history[..2]).is_err());
+
+        // Out of order
+        let (_, mut reordered) = ticket_with_history();
+        reordered.swap(0, 1);
+        assert!(verify_provenance(&ticket, &reordered).is_err());
+
+        // Rewritten resale price
+        let (_, mut repriced) = ticket_with_history();
+        repriced[1].sale_price = 1;
+        assert!(verify_provenance(&ticket, &repriced).is_err());
+
+        // Spliced in a different owner
+        let (_, mut spliced) = ticket_with_history();
+        spliced[2].previous_owner_commitment = [9u8; 32];
+        assert!(verify_provenance(&ticket, &spliced).is_err());
+    }
+}
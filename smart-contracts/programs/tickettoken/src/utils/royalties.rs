@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::types::Creator;
+use crate::errors::TicketTokenError;
+use crate::state::PayoutWallet;
+
+/// Resale royalty terms written into every ticket cNFT of an event
+#[derive(Clone, Debug, PartialEq)]
+pub struct Royalties {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<Creator>,
+}
+
+/// Build the royalty bps and creator shares from the artist, venue and
+/// platform cuts. The venue cut is divided across its payout wallets by
+/// weight. Parties with a zero cut are left out of the creators.
+pub fn calculate_royalties(
+    artist_wallet: Pubkey,
+    artist_bps: u16,
+    venue_wallets: &[PayoutWallet],
+    venue_bps: u16,
+    platform_treasury: Pubkey,
+    platform_bps: u16,
+) -> Result<Royalties> {
+    let total_bps = artist_bps as u32 + venue_bps as u32 + platform_bps as u32;
+    require!(
+        total_bps <= 10_000,
+        TicketTokenError::InvalidRoyaltyPercentage
+    );
+    require!(
+        artist_bps == 0 || artist_wallet != Pubkey::default(),
+        TicketTokenError::InvalidArtistWallet
+    );
+
+    // Rounding dust of the venue cut stays with its first wallet
+    let mut venue_parties: Vec<(Pubkey, u16)> = venue_wallets
+        .iter()
+        .map(|payout| (payout.wallet, (venue_bps as u32 * payout.weight_bps as u32 / 10_000) as u16))
+        .collect();
+    let venue_allocated: u16 = venue_parties.iter().map(|(_, bps)| bps).sum();
+    if let Some(first) = venue_parties.first_mut() {
+        first.1 += venue_bps - venue_allocated;
+    }
+
+    let parties: Vec<(Pubkey, u16)> = std::iter::once((artist_wallet, artist_bps))
+        .chain(venue_parties)
+        .chain(std::iter::once((platform_treasury, platform_bps)))
+        .filter(|(_, bps)| *bps > 0)
+        .collect();
+
+    if parties.is_empty() {
+        return Ok(Royalties {
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+        });
+    }
+
+    // Bubblegum rejects duplicate creator addresses
+    for (i, (address, _)) in parties.iter().enumerate() {
+        require!(
+            parties[..i].iter().all(|(other, _)| other != address),
+            TicketTokenError::DuplicateRoyaltyRecipient
+        );
+    }
+
+    // Shares are whole percentages that must sum to 100; the rounding
+    // remainder goes to the party with the largest cut
+    let mut creators: Vec<Creator> = parties
+        .iter()
+        .map(|(address, bps)| Creator {
+            address: *address,
+            verified: false,
+            share: (*bps as u32 * 100 / total_bps) as u8,
+        })
+        .collect();
+    require!(
+        creators.iter().all(|creator| creator.share > 0),
+        TicketTokenError::RoyaltyShareTooSmall
+    );
+
+    let allocated: u32 = creators.iter().map(|creator| creator.share as u32).sum();
+    let largest = parties
+        .iter()
+        .enumerate()
+        .max_by_key(|(i, (_, bps))| (*bps, std::cmp::Reverse(*i)))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    creators[largest].share += (100 - allocated) as u8;
+
+    Ok(Royalties {
+        seller_fee_basis_points: total_bps as u16,
+        creators,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sole(wallet: Pubkey) -> Vec<PayoutWallet> {
+        vec![PayoutWallet { wallet, weight_bps: 10_000 }]
+    }
+
+    #[test]
+    fn test_royalties_split_by_cut() {
+        let artist = Pubkey::new_unique();
+        let venue = Pubkey::new_unique();
+        let platform = Pubkey::new_unique();
+
+        let royalties = calculate_royalties(artist, 500, &sole(venue), 300, platform, 200).unwrap();
+
+        assert_eq!(royalties.seller_fee_basis_points, 1000);
+        let shares: Vec<(Pubkey, u8)> = royalties.creators.iter().map(|c| (c.address, c.share)).collect();
+        assert_eq!(shares, vec![(artist, 50), (venue, 30), (platform, 20)]);
+    }
+
+    #[test]
+    fn test_royalties_rounding_goes_to_largest_cut() {
+        let royalties = calculate_royalties(
+            Pubkey::new_unique(), 100,
+            &sole(Pubkey::new_unique()), 100,
+            Pubkey::new_unique(), 150,
+        ).unwrap();
+
+        let shares: Vec<u8> = royalties.creators.iter().map(|c| c.share).collect();
+        assert_eq!(shares, vec![28, 28, 44]);
+    }
+
+    #[test]
+    fn test_royalties_skip_zero_cuts() {
+        let venue = Pubkey::new_unique();
+        let royalties = calculate_royalties(Pubkey::default(), 0, &sole(venue), 500, Pubkey::new_unique(), 0).unwrap();
+
+        assert_eq!(royalties.seller_fee_basis_points, 500);
+        assert_eq!(royalties.creators.len(), 1);
+        assert_eq!(royalties.creators[0].address, venue);
+        assert_eq!(royalties.creators[0].share, 100);
+
+        let none = calculate_royalties(Pubkey::default(), 0, &sole(venue), 0, Pubkey::new_unique(), 0).unwrap();
+        assert_eq!(none.seller_fee_basis_points, 0);
+        assert!(none.creators.is_empty());
+    }
+
+    #[test]
+    fn test_royalties_reject_invalid_configs() {
+        let venue = Pubkey::new_unique();
+        let venue_wallets = sole(venue);
+        let platform = Pubkey::new_unique();
+
+        // Over 100%
+        assert!(calculate_royalties(Pubkey::new_unique(), 6000, &venue_wallets, 4000, platform, 100).is_err());
+        // Artist cut without a wallet
+        assert!(calculate_royalties(Pubkey::default(), 500, &venue_wallets, 500, platform, 100).is_err());
+        // Same wallet twice
+        assert!(calculate_royalties(venue, 500, &venue_wallets, 500, platform, 100).is_err());
+        // A cut too small to earn a whole percent share
+        assert!(calculate_royalties(Pubkey::new_unique(), 5000, &venue_wallets, 4900, platform, 1).is_err());
+    }
+
+    #[test]
+    fn test_royalties_split_venue_cut_by_weight() {
+        let artist = Pubkey::new_unique();
+        let platform = Pubkey::new_unique();
+        let venue_wallets = vec![
+            PayoutWallet { wallet: Pubkey::new_unique(), weight_bps: 7_000 },
+            PayoutWallet { wallet: Pubkey::new_unique(), weight_bps: 3_000 },
+        ];
+
+        let royalties = calculate_royalties(artist, 500, &venue_wallets, 300, platform, 200).unwrap();
+
+        assert_eq!(royalties.seller_fee_basis_points, 1000);
+        let shares: Vec<(Pubkey, u8)> = royalties.creators.iter().map(|c| (c.address, c.share)).collect();
+        assert_eq!(shares, vec![
+            (artist, 50),
+            (venue_wallets[0].wallet, 21),
+            (venue_wallets[1].wallet, 9),
+            (platform, 20),
+        ]);
+    }
+}
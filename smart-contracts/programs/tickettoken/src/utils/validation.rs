@@ -60,3 +60,30 @@ pub fn validate_refund_window(window: i64) -> Result<()> {
     
     Ok(())
 }
+
+pub fn validate_checkin_window(doors_open: i64, checkin_close: i64) -> Result<()> {
+    require!(
+        (0..=MAX_CHECKIN_OFFSET).contains(&doors_open)
+            && (0..=MAX_CHECKIN_OFFSET).contains(&checkin_close),
+        TicketTokenError::InvalidCheckInWindow
+    );
+    
+    Ok(())
+}
+
+pub fn validate_venue_profile(name: &str, metadata_uri: &str) -> Result<()> {
+    require!(
+        name.len() <= MAX_VENUE_NAME,
+        TicketTokenError::VenueNameTooLong
+    );
+    require!(
+        metadata_uri.len() <= MAX_URI_LENGTH,
+        TicketTokenError::UriTooLong
+    );
+    require!(
+        name.chars().all(|c| c.is_ascii_graphic() || c == ' '),
+        TicketTokenError::InvalidCharacters
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,14 @@
+#!/usr/bin/env bash
+# Dumps the Bubblegum, account-compression, noop and token-metadata programs from mainnet into
+# tests/fixtures, where the LiteSVM test harness loads them (see tests/utils/svm.ts). Commit the output.
+set -euo pipefail
+
+FIXTURES_DIR="$(dirname "$0")/../tests/fixtures"
+mkdir -p "$FIXTURES_DIR"
+
+solana program dump -u m BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY "$FIXTURES_DIR/mpl_bubblegum.so"
+solana program dump -u m cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK "$FIXTURES_DIR/spl_account_compression.so"
+solana program dump -u m noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV "$FIXTURES_DIR/spl_noop.so"
+solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s "$FIXTURES_DIR/mpl_token_metadata.so"
+
+echo "Fixtures written to $FIXTURES_DIR"
@@ -1,14 +1,68 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { ComputeBudgetProgram, PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { LiteSVMProvider } from "anchor-litesvm";
 import { assert } from "chai";
+import { createHash } from "crypto";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  BUBBLEGUM_PROGRAM_ID,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  SPL_NOOP_PROGRAM_ID,
+  TOKEN_METADATA_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  fetchNumMinted,
+  findAssetId,
+  findAssociatedTokenAddress,
+  findBubblegumSignerPda,
+  findMasterEditionPda,
+  findMetadataPda,
+  findTreeConfigPda,
+  merkleTreeAccountSize,
+  metadataArg,
+  ticketLeaf,
+  ticketRoyalties,
+  TicketLeaf,
+  TicketTree,
+} from "./utils/bubblegum";
+import { balanceOf, isClosed, startSvm, unixNow, warp } from "./utils/svm";
+import idl from "../target/idl/tickettoken.json";
 
 describe("tickettoken", () => {
-  // Configure the client to use the local cluster
-  const provider = anchor.AnchorProvider.env();
+  // Run against an in-process SVM so tests can move the clock
+  const svm = startSvm(new PublicKey(idl.address));
+  const provider = new LiteSVMProvider(svm);
   anchor.setProvider(provider);
+  // Pays rent, fees and every ticket purchase
+  svm.airdrop(provider.wallet.publicKey, BigInt(1_000 * anchor.web3.LAMPORTS_PER_SOL));
 
-  const program = anchor.workspace.Tickettoken as Program;
+  const program = new Program(idl as anchor.Idl, provider);
+
+  const findEventVaultPda = (event: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("event_vault"), event.toBuffer()], program.programId)[0];
+  const [roleRegistryPda] = PublicKey.findProgramAddressSync([Buffer.from("role_registry")], program.programId);
+  const findSettlementPda = (event: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("settlement"), event.toBuffer()], program.programId)[0];
+  const findTicketPda = (event: PublicKey, id: anchor.BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("ticket"), event.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  const findEventTreePda = (index: number, event = eventPda) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("event_tree"), event.toBuffer(), Buffer.from([index])],
+      program.programId
+    )[0];
+  const findSeatMapPda = (section: string, event = eventPda) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("seat_map"), event.toBuffer(), Buffer.from(section)],
+      program.programId
+    )[0];
+  const findTierPda = (index: number, event = eventPda) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("ticket_tier"), event.toBuffer(), Buffer.from([index])],
+      program.programId
+    )[0];
 
   // Test accounts
   const authority = provider.wallet as anchor.Wallet;
@@ -34,11 +88,98 @@ describe("tickettoken", () => {
   const artistPercentage = 500; // 5% in basis points
   const venuePercentage = 300; // 3% in basis points
 
-  const ticketId = new anchor.BN(1);
-  const nftAssetId = Keypair.generate().publicKey;
+  const ticketId = new anchor.BN(2); // Sold in the rollover order, not yet registered
   const ownerId = "user-12345678";
   const newOwnerId = "user-87654321";
 
+  // Tickets only store sha256(salt || ownerId); the salt stays off-chain
+  const ownerSalt = Buffer.alloc(32, 7);
+  const commit = (id: string) => [...createHash("sha256").update(ownerSalt).update(id).digest()];
+
+  // A small first tree so the second purchase spills onto the next one
+  const firstTree = Keypair.generate();
+  const secondTree = Keypair.generate();
+  const eventTrees = [new TicketTree(firstTree.publicKey, 3), new TicketTree(secondTree.publicKey, 7)];
+  // Leaves of the main event's sold tickets, by ticket number
+  const soldLeaves: TicketLeaf[] = [];
+
+  // Record tickets bought by the authority, with the metadata the program minted
+  const recordSale = (treeIndex: number, firstNonce: number, firstTicket: number, quantity: number, row: number, seatStart: number) => {
+    const sale = {
+      eventName,
+      metadataUri: "https://example.com/event-metadata.json",
+      tierName: "GA",
+      tierIndex: 0,
+      collectionMint: PublicKey.findProgramAddressSync(
+        [Buffer.from("collection_mint"), eventPda.toBuffer()],
+        program.programId
+      )[0],
+      // Venue cut goes to the owner until set_venue_payout is called
+      royalties: ticketRoyalties([
+        [artistWallet.publicKey, artistPercentage],
+        [authority.publicKey, venuePercentage],
+        [treasury.publicKey, 250],
+      ]),
+    };
+    for (let i = 0; i < quantity; i++) {
+      const leaf = ticketLeaf(sale, treeIndex, firstNonce + i, firstTicket + i, "A", row, seatStart + i);
+      soldLeaves[firstTicket + i] = leaf;
+      eventTrees[treeIndex].setOwner(leaf, authority.publicKey);
+    }
+  };
+  const leafProof = (ticketNumber: number) => eventTrees[soldLeaves[ticketNumber].treeIndex].proof(soldLeaves[ticketNumber]);
+
+  // Accounts that move a main-event ticket's leaf out of `leafOwner`, the authority's custodial wallet by default
+  const transferAccounts = (
+    ticketNumber: number,
+    newLeafOwner: PublicKey,
+    signer = authority.publicKey,
+    leafOwner = authority.publicKey
+  ) => {
+    const merkleTree = eventTrees[soldLeaves[ticketNumber].treeIndex].merkleTree;
+    return {
+      authority: signer,
+      platform: platformPda,
+      roleRegistry: roleRegistryPda,
+      event: eventPda,
+      venue: venuePda,
+      venueStaff: null,
+      ticket: findTicketPda(eventPda, new anchor.BN(ticketNumber)),
+      leafOwner,
+      newLeafOwner,
+      eventTree: findEventTreePda(soldLeaves[ticketNumber].treeIndex),
+      treeConfig: findTreeConfigPda(merkleTree),
+      merkleTree,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+  };
+
+  // Accounts that burn a main-event ticket's leaf held by `holder` for a refund
+  const refundAccounts = (ticketNumber: number, holder: PublicKey, ticketRentPayer: PublicKey | null) => {
+    const leaf = soldLeaves[ticketNumber];
+    const merkleTree = eventTrees[leaf.treeIndex].merkleTree;
+    return {
+      holder,
+      platform: platformPda,
+      event: eventPda,
+      eventVault: findEventVaultPda(eventPda),
+      tier: findTierPda(leaf.tierIndex),
+      seatMap: findSeatMapPda(leaf.section),
+      ticket: findTicketPda(eventPda, new anchor.BN(ticketNumber)),
+      ticketRentPayer,
+      eventTree: findEventTreePda(leaf.treeIndex),
+      treeConfig: findTreeConfigPda(merkleTree),
+      merkleTree,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+  };
+
   before(async () => {
     // Derive Platform PDA
     [platformPda] = PublicKey.findProgramAddressSync(
@@ -71,6 +212,40 @@ describe("tickettoken", () => {
         console.log("Platform may already be initialized:", error.message);
       }
     });
+
+    it("should only let the owner initialize the role registry", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .initializeRoleRegistry()
+          .accounts({
+            owner: stranger.publicKey,
+            platform: platformPda,
+            roleRegistry: roleRegistryPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for a non-owner");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+
+      await program.methods
+        .initializeRoleRegistry()
+        .accounts({
+          owner: authority.publicKey,
+          platform: platformPda,
+          roleRegistry: roleRegistryPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const registry = await program.account.roleRegistry.fetch(roleRegistryPda);
+      assert.equal(registry.members.length, 0);
+      console.log("✓ Role registry initialized by the platform owner");
+    });
   });
 
   describe("2. Create Venue", () => {
@@ -114,6 +289,7 @@ describe("tickettoken", () => {
           .accounts({
             authority: authority.publicKey,
             platform: platformPda,
+            roleRegistry: roleRegistryPda,
             venue: venuePda,
           })
           .rpc();
@@ -131,10 +307,10 @@ describe("tickettoken", () => {
 
   describe("4. Create Event with Royalties", () => {
     it("should create an event with artist and venue royalty percentages", async () => {
-      const now = Math.floor(Date.now() / 1000);
+      const now = unixNow(svm);
       // startTime must be > now + 3600 (1 hour in future)
-      // verification window is startTime - 3600 to endTime + 3600
-      // So if startTime = now + 3601, verification window starts at now + 1
+      // check-in window is startTime - doorsOpen to endTime + checkinClose
+      // So with doorsOpen = 3600 and startTime = now + 3601, check-in starts at now + 1
       const startTime = new anchor.BN(now + 3601); // Just over 1 hour from now
       const endTime = new anchor.BN(now + 7200); // 2 hours from now
       const refundWindow = new anchor.BN(3600); // 1 hour
@@ -159,6 +335,14 @@ describe("tickettoken", () => {
         startTime: startTime,
         endTime: endTime,
         refundWindow: refundWindow,
+        doorsOpen: new anchor.BN(3600),
+        checkinClose: new anchor.BN(3600),
+        selfCustody: false,
+        // Full refund until a week out, half until the doors open
+        refundSchedule: [
+          { cutoff: new anchor.BN(7 * 24 * 3600), refundBps: 10000 },
+          { cutoff: new anchor.BN(0), refundBps: 5000 },
+        ],
         metadataUri: "https://example.com/event-metadata.json",
         oracleFeed: PublicKey.default, // Placeholder
         description: "An amazing test concert event",
@@ -173,9 +357,12 @@ describe("tickettoken", () => {
         .createEvent(eventParams)
         .accounts({
           authority: authority.publicKey,
+          platform: platformPda,
           venue: venuePda,
+          venueStaff: null,
           event: eventPda,
           reentrancyGuard: reentrancyGuardPda,
+          eventVault: findEventVaultPda(eventPda),
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -190,8 +377,11 @@ describe("tickettoken", () => {
 
       // Verify royalty fields
       assert.ok(eventAccount.artistWallet.equals(artistWallet.publicKey), "Artist wallet mismatch");
+      assert.equal(eventAccount.platformFeeBps, 250, "Platform fee not snapshotted");
       assert.equal(eventAccount.artistPercentage, artistPercentage, "Artist percentage mismatch");
       assert.equal(eventAccount.venuePercentage, venuePercentage, "Venue percentage mismatch");
+      assert.equal(eventAccount.refundSteps, 2, "Refund schedule mismatch");
+      assert.equal(eventAccount.refundSchedule[1].refundBps, 5000, "Refund schedule mismatch");
 
       console.log("✓ Event created with royalties:");
       console.log("  - Artist:", artistPercentage, "bps");
@@ -199,7 +389,7 @@ describe("tickettoken", () => {
     });
 
     it("should reject event creation with invalid royalty percentages (> 100%)", async () => {
-      const now = Math.floor(Date.now() / 1000);
+      const now = unixNow(svm);
       const badEventId = new anchor.BN(999);
 
       const [badEventPda] = PublicKey.findProgramAddressSync(
@@ -220,6 +410,10 @@ describe("tickettoken", () => {
         startTime: new anchor.BN(now + 86400),
         endTime: new anchor.BN(now + 90000),
         refundWindow: new anchor.BN(3600),
+        doorsOpen: new anchor.BN(3600),
+        checkinClose: new anchor.BN(3600),
+        selfCustody: false,
+        refundSchedule: [],
         metadataUri: "https://example.com/bad-event.json",
         oracleFeed: PublicKey.default,
         description: "This should fail",
@@ -235,9 +429,12 @@ describe("tickettoken", () => {
           .createEvent(invalidEventParams)
           .accounts({
             authority: authority.publicKey,
+            platform: platformPda,
             venue: venuePda,
+            venueStaff: null,
             event: badEventPda,
             reentrancyGuard: badReentrancyPda,
+            eventVault: findEventVaultPda(badEventPda),
             systemProgram: SystemProgram.programId,
           })
           .rpc();
@@ -248,209 +445,1812 @@ describe("tickettoken", () => {
         console.log("✓ Correctly rejected royalties > 100%");
       }
     });
-  });
 
-  describe("5. Register Ticket", () => {
-    it("should register a ticket after NFT minting", async () => {
-      // Derive Ticket PDA
-      [ticketPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("ticket"), eventPda.toBuffer(), ticketId.toArrayLike(Buffer, "le", 8)],
+    it("should reject an artist wallet that duplicates another royalty recipient", async () => {
+      const now = unixNow(svm);
+      const badEventId = new anchor.BN(998);
+
+      const [badEventPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("event"), venuePda.toBuffer(), badEventId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
-      await program.methods
-        .registerTicket(ticketId, nftAssetId, ownerId)
-        .accounts({
-          authority: authority.publicKey,
-          event: eventPda,
-          ticket: ticketPda,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
+      const [badReentrancyPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reentrancy"), badEventPda.toBuffer()],
+        program.programId
+      );
 
-      // Fetch and verify ticket account
-      const ticketAccount = await program.account.ticket.fetch(ticketPda);
-      assert.ok(ticketAccount.event.equals(eventPda));
-      assert.equal(ticketAccount.ticketId.toNumber(), ticketId.toNumber());
-      assert.ok(ticketAccount.nftAssetId.equals(nftAssetId));
-      assert.equal(ticketAccount.currentOwnerId, ownerId);
-      assert.equal(ticketAccount.used, false);
-      assert.equal(ticketAccount.transferCount, 0);
-      assert.equal(ticketAccount.verifiedAt, null);
+      try {
+        await program.methods
+          .createEvent({
+            eventId: badEventId,
+            name: "Bad Event",
+            ticketPrice: ticketPrice,
+            totalTickets: 50,
+            startTime: new anchor.BN(now + 86400),
+            endTime: new anchor.BN(now + 90000),
+            refundWindow: new anchor.BN(3600),
+            doorsOpen: new anchor.BN(3600),
+            checkinClose: new anchor.BN(3600),
+            selfCustody: false,
+            refundSchedule: [],
+            metadataUri: "https://example.com/bad-event.json",
+            oracleFeed: PublicKey.default,
+            description: "This should fail",
+            transferable: true,
+            resaleable: true,
+            artistWallet: authority.publicKey, // Also the venue owner
+            artistPercentage: artistPercentage,
+            venuePercentage: venuePercentage,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            platform: platformPda,
+            venue: venuePda,
+            venueStaff: null,
+            event: badEventPda,
+            reentrancyGuard: badReentrancyPda,
+            eventVault: findEventVaultPda(badEventPda),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
 
-      console.log("✓ Ticket registered:");
-      console.log("  - Ticket ID:", ticketId.toString());
-      console.log("  - Owner:", ownerId);
-      console.log("  - Used:", false);
+        assert.fail("Should have thrown an error for duplicate royalty recipient");
+      } catch (error) {
+        assert.include(error.toString(), "DuplicateRoyaltyRecipient");
+        console.log("✓ Correctly rejected duplicate royalty recipient");
+      }
     });
   });
 
-  describe("6. Transfer Ticket", () => {
-    it("should transfer ticket ownership (simulating resale)", async () => {
-      await program.methods
-        .transferTicket(newOwnerId)
+  describe("5. Purchase Tickets (compressed NFT minting)", () => {
+    let treeCreatorPda: PublicKey;
+    let collectionAuthorityPda: PublicKey;
+    let collectionMintPda: PublicKey;
+
+    const purchaseAccounts = (active: Keypair, activeIndex: number, next: Keypair | null = null) => ({
+      buyer: authority.publicKey,
+      platform: platformPda,
+      venue: venuePda,
+      event: eventPda,
+      reentrancyGuard: reentrancyGuardPda,
+      eventVault: findEventVaultPda(eventPda),
+      tier: findTierPda(0),
+      seatMap: findSeatMapPda("A"),
+      eventTree: findEventTreePda(activeIndex),
+      treeConfig: findTreeConfigPda(active.publicKey),
+      merkleTree: active.publicKey,
+      nextEventTree: next ? findEventTreePda(activeIndex + 1) : null,
+      nextTreeConfig: next ? findTreeConfigPda(next.publicKey) : null,
+      nextMerkleTree: next ? next.publicKey : null,
+      treeCreator: treeCreatorPda,
+      collectionAuthority: collectionAuthorityPda,
+      collectionMint: collectionMintPda,
+      collectionMetadata: findMetadataPda(collectionMintPda),
+      collectionEdition: findMasterEditionPda(collectionMintPda),
+      bubblegumSigner: findBubblegumSignerPda(),
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    const initializeTree = (tree: Keypair, index: number, capacity: number, maxProofBytes: number) =>
+      program.methods
+        .initializeEventTree(capacity, maxProofBytes)
         .accounts({
           authority: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: null,
           event: eventPda,
-          ticket: ticketPda,
+          eventTree: findEventTreePda(index),
+          treeConfig: findTreeConfigPda(tree.publicKey),
+          merkleTree: tree.publicKey,
+          treeCreator: treeCreatorPda,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
+        .signers([tree])
         .rpc();
 
-      // Fetch and verify ticket was transferred
-      const ticketAccount = await program.account.ticket.fetch(ticketPda);
-      assert.equal(ticketAccount.currentOwnerId, newOwnerId);
-      assert.equal(ticketAccount.transferCount, 1);
-      assert.equal(ticketAccount.used, false); // Still not used
-
-      console.log("✓ Ticket transferred:");
-      console.log("  - New Owner:", newOwnerId);
-      console.log("  - Transfer Count:", ticketAccount.transferCount);
+    before(async () => {
+      [treeCreatorPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("tree_creator"), eventPda.toBuffer()],
+        program.programId
+      );
+      [collectionAuthorityPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collection_authority"), eventPda.toBuffer()],
+        program.programId
+      );
+      [collectionMintPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collection_mint"), eventPda.toBuffer()],
+        program.programId
+      );
     });
 
-    it("should allow multiple transfers and increment count", async () => {
-      const thirdOwnerId = "user-99999999";
-
+    it("should create a general admission tier", async () => {
       await program.methods
-        .transferTicket(thirdOwnerId)
+        .createTicketTier({
+          name: "GA",
+          price: ticketPrice,
+          capacity: totalTickets,
+          saleStart: new anchor.BN(0),
+          saleEnd: new anchor.BN(0),
+        })
         .accounts({
           authority: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: null,
           event: eventPda,
-          ticket: ticketPda,
+          tier: findTierPda(0),
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      const ticketAccount = await program.account.ticket.fetch(ticketPda);
-      assert.equal(ticketAccount.currentOwnerId, thirdOwnerId);
-      assert.equal(ticketAccount.transferCount, 2);
+      const tier = await program.account.ticketTier.fetch(findTierPda(0));
+      assert.equal(tier.index, 0);
+      assert.equal(tier.capacity, totalTickets);
+      assert.equal(tier.sold, 0);
 
-      console.log("✓ Ticket transferred again - Count:", ticketAccount.transferCount);
-    });
-  });
+      const eventAccount = await program.account.event.fetch(eventPda);
+      assert.equal(eventAccount.tierCount, 1);
 
-  describe("7. Verify Ticket (Mark as USED)", () => {
-    it("should mark ticket as used when verified at door", async () => {
-      // Wait 2 seconds to ensure we're in the verification window
-      // (startTime - 3600 = now + 1, so after 2 seconds we're in the window)
-      await new Promise(resolve => setTimeout(resolve, 2000));
+      console.log("✓ GA tier created");
+    });
 
+    it("should initialize a seat map for section A", async () => {
       await program.methods
-        .verifyTicket()
+        .initializeSeatMap("A", 10, 20, 0b1)
         .accounts({
-          validator: authority.publicKey,
+          authority: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: null,
           event: eventPda,
-          ticket: ticketPda,
+          seatMap: findSeatMapPda("A"),
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      // Fetch and verify ticket is marked as used
-      const ticketAccount = await program.account.ticket.fetch(ticketPda);
-      assert.equal(ticketAccount.used, true);
-      assert.notEqual(ticketAccount.verifiedAt, null);
+      const seatMap = await program.account.seatMap.fetch(findSeatMapPda("A"));
+      assert.equal(seatMap.rows, 10);
+      assert.equal(seatMap.seatsPerRow, 20);
+      assert.equal(seatMap.seatsSold, 0);
+      assert.equal(seatMap.sold.length, 25);
+      assert.equal(seatMap.tiers, 0b1);
 
-      console.log("✓ Ticket verified and marked as USED");
-      console.log("  - Verified At:", new Date(ticketAccount.verifiedAt.toNumber() * 1000).toISOString());
+      console.log("✓ Seat map initialized: section A, 10 x 20");
     });
 
-    it("should reject verification of already used ticket", async () => {
+    it("should reject purchases before the event tree exists", async () => {
       try {
         await program.methods
-          .verifyTicket()
-          .accounts({
-            validator: authority.publicKey,
-            event: eventPda,
-            ticket: ticketPda,
-          })
+          .purchaseTickets({ tierIndex: 0, quantity: 1, section: "A", row: 1, seatStart: 1, ownerCommitments: [] })
+          .accounts(purchaseAccounts(firstTree, 0))
           .rpc();
 
-        assert.fail("Should have thrown an error for already used ticket");
+        assert.fail("Should have thrown an error for missing merkle tree");
       } catch (error) {
-        assert.include(error.toString(), "TicketAlreadyUsed");
-        console.log("✓ Correctly rejected double-scan of used ticket");
+        assert.include(error.toString(), "TreeNotInitialized");
+        console.log("✓ Correctly rejected purchase without a tree");
       }
     });
 
-    it("should reject transfer of used ticket", async () => {
-      const anotherOwnerId = "user-00000000";
+    it("should size event trees from the requested capacity", async () => {
+      // 8 leaves (depth 3) with a full proof budget, so no canopy
+      await initializeTree(firstTree, 0, 8, 96);
+      await initializeTree(secondTree, 1, totalTickets - 8, 224);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      assert.ok(eventAccount.merkleTree.equals(firstTree.publicKey));
+      assert.equal(eventAccount.treeCount, 2);
+      assert.equal(eventAccount.activeTree, 0);
+
+      const firstAccount = svm.getAccount(firstTree.publicKey);
+      assert.ok(firstAccount.owner.equals(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID));
+      assert.equal(firstAccount.data.length, merkleTreeAccountSize(3, 8));
+
+      const secondAccount = svm.getAccount(secondTree.publicKey);
+      assert.equal(secondAccount.data.length, merkleTreeAccountSize(7, 16));
+
+      const eventTree = await program.account.eventTree.fetch(findEventTreePda(1));
+      assert.equal(eventTree.index, 1);
+      assert.equal(eventTree.capacity, 128);
+      assert.equal(eventTree.refunded.length, 16);
 
+      console.log("✓ Event trees initialized:", firstTree.publicKey.toBase58(), secondTree.publicKey.toBase58());
+    });
+
+    it("should reject trees larger than the per-tree capacity cap", async () => {
+      // 65,536 leaves per tree; stadium events roll over into more trees
       try {
-        await program.methods
-          .transferTicket(anotherOwnerId)
-          .accounts({
-            authority: authority.publicKey,
-            event: eventPda,
-            ticket: ticketPda,
-          })
-          .rpc();
+        await initializeTree(Keypair.generate(), 2, 65_537, 320);
 
-        assert.fail("Should have thrown an error for transferring used ticket");
+        assert.fail("Should have thrown an error for an oversized tree");
       } catch (error) {
-        assert.include(error.toString(), "TicketAlreadyUsed");
-        console.log("✓ Correctly rejected transfer of used ticket");
+        assert.include(error.toString(), "InvalidCapacity");
+        console.log("✓ Correctly rejected a tree over the capacity cap");
       }
     });
-  });
 
-  describe("8. Integration Test - Full Ticket Lifecycle", () => {
-    it("should complete full lifecycle: register → transfer → verify", async () => {
-      const newTicketId = new anchor.BN(2);
-      const newNftAssetId = Keypair.generate().publicKey;
-      const initialOwner = "user-lifecycle-001";
-      const buyerOwner = "user-lifecycle-002";
+    it("should reject purchases before the event collection exists", async () => {
+      try {
+        await program.methods
+          .purchaseTickets({ tierIndex: 0, quantity: 1, section: "A", row: 1, seatStart: 1, ownerCommitments: [] })
+          .accounts(purchaseAccounts(firstTree, 0))
+          .rpc();
 
-      // Register new ticket
-      const [newTicketPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("ticket"), eventPda.toBuffer(), newTicketId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+        assert.fail("Should have thrown an error for missing collection");
+      } catch (error) {
+        assert.include(error.toString(), "CollectionNotInitialized");
+        console.log("✓ Correctly rejected purchase without a collection");
+      }
+    });
 
+    it("should create the event's collection NFT", async () => {
       await program.methods
-        .registerTicket(newTicketId, newNftAssetId, initialOwner)
+        .createEventCollection()
         .accounts({
           authority: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: null,
           event: eventPda,
-          ticket: newTicketPda,
+          collectionAuthority: collectionAuthorityPda,
+          collectionMint: collectionMintPda,
+          collectionTokenAccount: findAssociatedTokenAddress(collectionAuthorityPda, collectionMintPda),
+          collectionMetadata: findMetadataPda(collectionMintPda),
+          collectionEdition: findMasterEditionPda(collectionMintPda),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
         .rpc();
 
-      let ticket = await program.account.ticket.fetch(newTicketPda);
-      assert.equal(ticket.currentOwnerId, initialOwner);
-      assert.equal(ticket.transferCount, 0);
-      assert.equal(ticket.used, false);
+      const eventAccount = await program.account.event.fetch(eventPda);
+      assert.ok(eventAccount.collectionMint.equals(collectionMintPda));
+
+      const metadataAccount = svm.getAccount(findMetadataPda(collectionMintPda));
+      assert.ok(metadataAccount.owner.equals(TOKEN_METADATA_PROGRAM_ID));
+      // key (1) + update_authority (32)
+      assert.ok(new PublicKey(metadataAccount.data.subarray(1, 33)).equals(collectionAuthorityPda));
+
+      console.log("✓ Event collection created:", collectionMintPda.toBase58());
+    });
+
+    it("should mint one compressed NFT per purchased ticket", async () => {
+      const quantity = 2;
+      const treeConfig = findTreeConfigPda(firstTree.publicKey);
+      const mintedBefore = fetchNumMinted(svm, treeConfig);
+
+      // Ticket PDAs for #0 and #1 are created alongside the sale; #1 is
+      // held only by the buyer's wallet, so its commitment is empty
+      const ticketPdas = [0, 1].map((n) => findTicketPda(eventPda, new anchor.BN(n)));
+      const ownerCommitments = [commit(ownerId), new Array(32).fill(0)];
+
+      await program.methods
+        .purchaseTickets({ tierIndex: 0, quantity, section: "A", row: 1, seatStart: 1, ownerCommitments })
+        .accounts(purchaseAccounts(firstTree, 0))
+        .remainingAccounts(ticketPdas.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .rpc();
+
+      const mintedAfter = fetchNumMinted(svm, treeConfig);
+      assert.equal(mintedAfter - mintedBefore, quantity);
+      recordSale(0, 0, 0, quantity, 1, 1);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      assert.equal(eventAccount.ticketsSold, quantity);
+
+      const tier = await program.account.ticketTier.fetch(findTierPda(0));
+      assert.equal(tier.sold, quantity);
+
+      const vault = await program.account.eventVault.fetch(findEventVaultPda(eventPda));
+      assert.equal(vault.grossSales.toString(), ticketPrice.muln(quantity).toString());
+
+      for (const [n, pda] of ticketPdas.entries()) {
+        const ticket = await program.account.ticket.fetch(pda);
+        assert.equal(ticket.ticketId.toNumber(), n);
+        assert.deepEqual(ticket.ownerCommitment, ownerCommitments[n]);
+        assert.ok(ticket.ownerWallet.equals(authority.publicKey));
+        assert.equal(ticket.tierIndex, 0);
+        assert.ok(ticket.rentPayer.equals(authority.publicKey));
+        assert.equal(ticket.used, false);
+      }
+
+      console.log("✓ Minted", quantity, "compressed tickets into", firstTree.publicKey.toBase58());
+    });
+
+    it("should reject a purchase of an already sold seat", async () => {
+      try {
+        await program.methods
+          .purchaseTickets({ tierIndex: 0, quantity: 2, section: "A", row: 1, seatStart: 2, ownerCommitments: [] })
+          .accounts(purchaseAccounts(firstTree, 0))
+          .rpc();
+
+        assert.fail("Should have thrown an error for a sold seat");
+      } catch (error) {
+        assert.include(error.toString(), "SeatAlreadySold");
+      }
+
+      // The conflicting order left seat 3 free
+      const seatMap = await program.account.seatMap.fetch(findSeatMapPda("A"));
+      assert.equal(seatMap.seatsSold, 2);
+      assert.equal(seatMap.sold[0], 0b011);
+
+      console.log("✓ Correctly rejected double-booked seat");
+    });
 
-      // Transfer ticket (simulating resale)
+    it("should reject a tier buying seats in a section it does not sell", async () => {
+      // The VIP section is reserved for tier 1, so GA (tier 0) can't buy into it
       await program.methods
-        .transferTicket(buyerOwner)
+        .initializeSeatMap("VIP", 2, 10, 0b10)
         .accounts({
           authority: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: null,
           event: eventPda,
-          ticket: newTicketPda,
+          seatMap: findSeatMapPda("VIP"),
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      ticket = await program.account.ticket.fetch(newTicketPda);
-      assert.equal(ticket.currentOwnerId, buyerOwner);
-      assert.equal(ticket.transferCount, 1);
+      try {
+        await program.methods
+          .purchaseTickets({ tierIndex: 0, quantity: 1, section: "VIP", row: 1, seatStart: 1, ownerCommitments: [] })
+          .accounts({ ...purchaseAccounts(firstTree, 0), seatMap: findSeatMapPda("VIP") })
+          .rpc();
 
-      // Verify at door
+        assert.fail("Should have thrown an error for a section outside the tier");
+      } catch (error) {
+        assert.include(error.toString(), "SectionNotInTier");
+        console.log("✓ Correctly rejected GA purchase in the VIP section");
+      }
+    });
+
+    it("should reject an order that overflows the active tree without a next tree", async () => {
+      try {
+        await program.methods
+          .purchaseTickets({ tierIndex: 0, quantity: 7, section: "A", row: 2, seatStart: 1, ownerCommitments: [] })
+          .accounts(purchaseAccounts(firstTree, 0))
+          .rpc();
+
+        assert.fail("Should have thrown an error for a full tree");
+      } catch (error) {
+        assert.include(error.toString(), "TreeFull");
+        console.log("✓ Correctly rejected overflowing purchase");
+      }
+    });
+
+    it("should roll over to the next tree when the active tree fills", async () => {
       await program.methods
-        .verifyTicket()
-        .accounts({
-          validator: authority.publicKey,
-          event: eventPda,
-          ticket: newTicketPda,
-        })
+        .purchaseTickets({ tierIndex: 0, quantity: 7, section: "A", row: 2, seatStart: 1, ownerCommitments: [] })
+        .accounts(purchaseAccounts(firstTree, 0, secondTree))
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_000_000 })])
         .rpc();
 
-      ticket = await program.account.ticket.fetch(newTicketPda);
-      assert.equal(ticket.used, true);
-      assert.notEqual(ticket.verifiedAt, null);
+      assert.equal(fetchNumMinted(svm, findTreeConfigPda(firstTree.publicKey)), 8);
+      assert.equal(fetchNumMinted(svm, findTreeConfigPda(secondTree.publicKey)), 1);
+      recordSale(0, 2, 2, 6, 2, 1);
+      recordSale(1, 0, 8, 1, 2, 7);
 
-      console.log("✓ Full lifecycle complete:");
-      console.log("  - Initial Owner:", initialOwner);
-      console.log("  - Final Owner:", buyerOwner);
-      console.log("  - Transfers:", ticket.transferCount);
-      console.log("  - Used:", ticket.used);
+      const eventAccount = await program.account.event.fetch(eventPda);
+      assert.equal(eventAccount.activeTree, 1);
+      assert.ok(eventAccount.merkleTree.equals(secondTree.publicKey));
+
+      const firstEventTree = await program.account.eventTree.fetch(findEventTreePda(0));
+      const secondEventTree = await program.account.eventTree.fetch(findEventTreePda(1));
+      assert.equal(firstEventTree.minted, 8);
+      assert.equal(secondEventTree.minted, 1);
+      assert.equal(firstEventTree.firstTicket, 0);
+      assert.equal(secondEventTree.firstTicket, 8);
+
+      console.log("✓ Rolled over to tree #1:", secondTree.publicKey.toBase58());
+    });
+  });
+
+  describe("6. Register Ticket", () => {
+    const registerAccounts = (signer: PublicKey, id: anchor.BN, leafOwner = authority.publicKey) => ({
+      authority: signer,
+      platform: platformPda,
+      roleRegistry: roleRegistryPda,
+      event: eventPda,
+      venue: venuePda,
+      venueStaff: null,
+      eventTree: findEventTreePda(0),
+      ticket: findTicketPda(eventPda, id),
+      leafOwner,
+      merkleTree: firstTree.publicKey,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    it("should reject registration of a ticket that was never sold", async () => {
+      const unsoldId = new anchor.BN(totalTickets);
+      const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+      try {
+        await program.methods
+          .registerTicket({ ...leafArgs, ticketNumber: totalTickets }, metadataArg(soldLeaves[2].metadata), commit(ownerId))
+          .accounts(registerAccounts(authority.publicKey, unsoldId))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for an unsold ticket");
+      } catch (error) {
+        assert.include(error.toString(), "TicketNotSold");
+        console.log("✓ Correctly rejected registration of an unsold ticket");
+      }
+    });
+
+    it("should reject registration under a tier the ticket was not bought in", async () => {
+      const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+      try {
+        await program.methods
+          .registerTicket({ ...leafArgs, tierIndex: 1 }, metadataArg(soldLeaves[2].metadata), commit(ownerId))
+          .accounts(registerAccounts(authority.publicKey, ticketId))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for a tier the leaf does not carry");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidTicketMetadata");
+        console.log("✓ Correctly rejected registration under another tier");
+      }
+    });
+
+    it("should reject registration to a wallet that does not hold the leaf", async () => {
+      const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+      try {
+        await program.methods
+          .registerTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit(ownerId))
+          .accounts(registerAccounts(authority.publicKey, ticketId, Keypair.generate().publicKey))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for a wallet that does not own the leaf");
+      } catch (error) {
+        assert.include(error.toString(), "ConcurrentMerkleTreeError");
+        console.log("✓ Correctly rejected registration to a non-holder");
+      }
+    });
+
+    it("should reject registration by a signer who is not a minter or venue staff", async () => {
+      const stranger = Keypair.generate();
+      const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+      try {
+        await program.methods
+          .registerTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit(ownerId))
+          .accounts(registerAccounts(stranger.publicKey, ticketId))
+          .remainingAccounts(proofAccounts)
+          // Fund the stranger so the account could be paid for
+          .preInstructions([
+            SystemProgram.transfer({
+              fromPubkey: authority.publicKey,
+              toPubkey: stranger.publicKey,
+              lamports: anchor.web3.LAMPORTS_PER_SOL / 10,
+            }),
+          ])
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for an unauthorized registrar");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+        console.log("✓ Correctly rejected unauthorized registration");
+      }
+    });
+
+    it("should register a sold ticket after NFT minting", async () => {
+      ticketPda = findTicketPda(eventPda, ticketId);
+      const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+
+      await program.methods
+        .registerTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit(ownerId))
+        .accounts(registerAccounts(authority.publicKey, ticketId))
+        .remainingAccounts(proofAccounts)
+        .rpc();
+
+      // Fetch and verify ticket account
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      assert.ok(ticketAccount.event.equals(eventPda));
+      assert.equal(ticketAccount.ticketId.toNumber(), ticketId.toNumber());
+      // Ticket #2 is leaf 2 of the first tree
+      const eventTree = await program.account.eventTree.fetch(findEventTreePda(0));
+      assert.ok(ticketAccount.nftAssetId.equals(findAssetId(eventTree.merkleTree, 2)));
+      assert.deepEqual(ticketAccount.ownerCommitment, commit(ownerId));
+      assert.equal(ticketAccount.tierIndex, 0);
+      // Custodial tickets are registered to the backend wallet holding the leaf
+      assert.ok(ticketAccount.ownerWallet.equals(authority.publicKey));
+      assert.equal(ticketAccount.used, false);
+      assert.equal(ticketAccount.transferCount, 0);
+      assert.equal(ticketAccount.verifiedAt, null);
+
+      console.log("✓ Ticket registered:");
+      console.log("  - Ticket ID:", ticketId.toString());
+      console.log("  - Owner:", ownerId);
+      console.log("  - Used:", false);
+    });
+  });
+
+  describe("7. Transfer Ticket", () => {
+    it("should transfer ticket ownership (simulating resale)", async () => {
+      const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+      await program.methods
+        .transferTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit(newOwnerId), new anchor.BN(0))
+        .accounts(transferAccounts(2, authority.publicKey))
+        .remainingAccounts(proofAccounts)
+        .rpc();
+
+      // Fetch and verify ticket was transferred
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      assert.deepEqual(ticketAccount.ownerCommitment, commit(newOwnerId));
+      assert.equal(ticketAccount.transferCount, 1);
+      assert.equal(ticketAccount.used, false); // Still not used
+
+      console.log("✓ Ticket transferred:");
+      console.log("  - New Owner:", newOwnerId);
+      console.log("  - Transfer Count:", ticketAccount.transferCount);
+    });
+
+    it("should only let a custodian move custodial tickets", async () => {
+      const stranger = Keypair.generate();
+      try {
+        const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+        await program.methods
+          .transferTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit("user-stolen"), new anchor.BN(0))
+          .accounts(transferAccounts(2, stranger.publicKey, stranger.publicKey))
+          .remainingAccounts(proofAccounts)
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for a transfer by a non-custodian");
+      } catch (error) {
+        assert.include(error.toString(), "NotTicketHolder");
+        console.log("✓ Correctly rejected transfer by a non-custodian");
+      }
+    });
+
+    it("should reject moving a ticket out of every wallet", async () => {
+      try {
+        const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+        await program.methods
+          .transferTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit("user-nowhere"), new anchor.BN(0))
+          .accounts(transferAccounts(2, PublicKey.default))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for a default owner wallet");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidOwnerWallet");
+        console.log("✓ Correctly rejected a transfer without an owner wallet");
+      }
+    });
+
+    it("should allow multiple transfers and increment count", async () => {
+      const thirdOwnerId = "user-99999999";
+      const before = await program.account.ticket.fetch(ticketPda);
+
+      const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+      await program.methods
+        .transferTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit(thirdOwnerId), new anchor.BN(0))
+        .accounts(transferAccounts(2, authority.publicKey))
+        .remainingAccounts(proofAccounts)
+        .rpc();
+
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      assert.deepEqual(ticketAccount.ownerCommitment, commit(thirdOwnerId));
+      assert.equal(ticketAccount.transferCount, 2);
+      // Every transfer folds into the ticket's provenance hash
+      assert.notDeepEqual(ticketAccount.provenanceHash, before.provenanceHash);
+
+      console.log("✓ Ticket transferred again - Count:", ticketAccount.transferCount);
+    });
+
+    it("should move the leaf with the ticket so the new holder can refund it", async () => {
+      const resold = 4;
+      const leaf = soldLeaves[resold];
+      const resoldPda = findTicketPda(eventPda, new anchor.BN(resold));
+      const holder = Keypair.generate();
+
+      let { leafArgs, proofAccounts } = leafProof(resold);
+      await program.methods
+        .registerTicket(leafArgs, metadataArg(leaf.metadata), commit(ownerId))
+        .accounts({
+          authority: authority.publicKey,
+          platform: platformPda,
+          roleRegistry: roleRegistryPda,
+          event: eventPda,
+          venue: venuePda,
+          venueStaff: null,
+          eventTree: findEventTreePda(leaf.treeIndex),
+          ticket: resoldPda,
+          leafOwner: authority.publicKey,
+          merkleTree: firstTree.publicKey,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(proofAccounts)
+        .rpc();
+
+      // Hand the ticket from the custodial wallet to the holder's own wallet
+      await program.methods
+        .transferTicket(leafArgs, metadataArg(leaf.metadata), commit(newOwnerId), new anchor.BN(0))
+        .accounts(transferAccounts(resold, holder.publicKey))
+        .remainingAccounts(proofAccounts)
+        .rpc();
+      eventTrees[leaf.treeIndex].setOwner(leaf, holder.publicKey);
+
+      const ticketAccount = await program.account.ticket.fetch(resoldPda);
+      assert.ok(ticketAccount.ownerWallet.equals(holder.publicKey));
+
+      // The custodial wallet no longer holds the leaf
+      ({ leafArgs, proofAccounts } = leafProof(resold));
+      try {
+        await program.methods
+          .refundTicket(leafArgs, metadataArg(leaf.metadata))
+          .accounts(refundAccounts(resold, authority.publicKey, authority.publicKey))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for a refund by the previous holder");
+      } catch (error) {
+        assert.include(error.toString(), "ConcurrentMerkleTreeError");
+      }
+
+      await program.methods
+        .refundTicket(leafArgs, metadataArg(leaf.metadata))
+        .accounts(refundAccounts(resold, holder.publicKey, authority.publicKey))
+        .remainingAccounts(proofAccounts)
+        .signers([holder])
+        .rpc();
+      eventTrees[leaf.treeIndex].setOwner(leaf, null);
+
+      // Inside a week of the show the schedule pays back half
+      assert.equal(balanceOf(svm, holder.publicKey), ticketPrice.toNumber() / 2);
+      assert.ok(isClosed(svm, resoldPda));
+
+      console.log("✓ Transferred ticket refunded to its new holder");
+    });
+  });
+
+  describe("8. Verify Ticket (Mark as USED)", () => {
+    it("should mark ticket as used when verified at door", async () => {
+      // Check-in opens at startTime - 3600 = now + 1, so step past it
+      warp(svm, 2);
+
+      await program.methods
+        .verifyTicket("Main Gate")
+        .accounts({
+          validator: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: null,
+          holder: null,
+          event: eventPda,
+          ticket: ticketPda,
+        })
+        .rpc();
+
+      // Fetch and verify ticket is marked as used
+      const ticketAccount = await program.account.ticket.fetch(ticketPda);
+      assert.equal(ticketAccount.used, true);
+      assert.notEqual(ticketAccount.verifiedAt, null);
+      assert.equal(Buffer.from(ticketAccount.verifiedGate).toString().replace(/\0/g, ""), "Main Gate");
+
+      console.log("✓ Ticket verified and marked as USED");
+      console.log("  - Verified At:", new Date(ticketAccount.verifiedAt.toNumber() * 1000).toISOString());
+    });
+
+    it("should reject scans by a signer who is not a venue scanner", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .verifyTicket("Side Door")
+          .accounts({
+            validator: stranger.publicKey,
+            platform: platformPda,
+            venue: venuePda,
+            venueStaff: null,
+            holder: null,
+            event: eventPda,
+            ticket: ticketPda,
+          })
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for an unauthorized scanner");
+      } catch (error) {
+        assert.include(error.toString(), "UnauthorizedScanner");
+        console.log("✓ Correctly rejected scan by an unauthorized signer");
+      }
+    });
+
+    it("should reject verification of already used ticket", async () => {
+      try {
+        await program.methods
+          .verifyTicket("Main Gate")
+          .accounts({
+            validator: authority.publicKey,
+            platform: platformPda,
+            venue: venuePda,
+            venueStaff: null,
+            holder: null,
+            event: eventPda,
+            ticket: ticketPda,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error for already used ticket");
+      } catch (error) {
+        assert.include(error.toString(), "TicketAlreadyUsed");
+        console.log("✓ Correctly rejected double-scan of used ticket");
+      }
+    });
+
+    it("should reject transfer of used ticket", async () => {
+      const anotherOwnerId = "user-00000000";
+
+      try {
+        const { leafArgs, proofAccounts } = leafProof(ticketId.toNumber());
+        await program.methods
+          .transferTicket(leafArgs, metadataArg(soldLeaves[2].metadata), commit(anotherOwnerId), new anchor.BN(0))
+          .accounts(transferAccounts(2, authority.publicKey))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for transferring used ticket");
+      } catch (error) {
+        assert.include(error.toString(), "TicketAlreadyUsed");
+        console.log("✓ Correctly rejected transfer of used ticket");
+      }
+    });
+  });
+
+  describe("9. Integration Test - Full Ticket Lifecycle", () => {
+    it("should complete full lifecycle: register → transfer → verify", async () => {
+      const newTicketId = new anchor.BN(3);
+      const initialOwner = "user-lifecycle-001";
+      const buyerOwner = "user-lifecycle-002";
+
+      // Register new ticket
+      const [newTicketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("ticket"), eventPda.toBuffer(), newTicketId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const { leafArgs, proofAccounts } = leafProof(newTicketId.toNumber());
+      await program.methods
+        .registerTicket(leafArgs, metadataArg(soldLeaves[3].metadata), commit(initialOwner))
+        .accounts({
+          authority: authority.publicKey,
+          platform: platformPda,
+          roleRegistry: roleRegistryPda,
+          event: eventPda,
+          venue: venuePda,
+          venueStaff: null,
+          eventTree: findEventTreePda(0),
+          ticket: newTicketPda,
+          leafOwner: authority.publicKey,
+          merkleTree: firstTree.publicKey,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(proofAccounts)
+        .rpc();
+
+      let ticket = await program.account.ticket.fetch(newTicketPda);
+      assert.deepEqual(ticket.ownerCommitment, commit(initialOwner));
+      assert.equal(ticket.transferCount, 0);
+      assert.equal(ticket.used, false);
+
+      // Transfer ticket (simulating resale); the leaf stays in the custodial wallet
+      await program.methods
+        .transferTicket(leafArgs, metadataArg(soldLeaves[3].metadata), commit(buyerOwner), ticketPrice)
+        .accounts(transferAccounts(3, authority.publicKey))
+        .remainingAccounts(proofAccounts)
+        .rpc();
+
+      ticket = await program.account.ticket.fetch(newTicketPda);
+      assert.deepEqual(ticket.ownerCommitment, commit(buyerOwner));
+      assert.equal(ticket.transferCount, 1);
+
+      const platformAccount = await program.account.platform.fetch(platformPda);
+      assert.equal(platformAccount.totalResales.toNumber(), 1);
+      assert.equal(platformAccount.totalResaleVolume.toString(), ticketPrice.toString());
+
+      // Verify at door
+      await program.methods
+        .verifyTicket("Main Gate")
+        .accounts({
+          validator: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: null,
+          holder: null,
+          event: eventPda,
+          ticket: newTicketPda,
+        })
+        .rpc();
+
+      ticket = await program.account.ticket.fetch(newTicketPda);
+      assert.equal(ticket.used, true);
+      assert.notEqual(ticket.verifiedAt, null);
+
+      console.log("✓ Full lifecycle complete:");
+      console.log("  - Initial Owner:", initialOwner);
+      console.log("  - Final Owner:", buyerOwner);
+      console.log("  - Transfers:", ticket.transferCount);
+      console.log("  - Used:", ticket.used);
+    });
+  });
+
+  describe("10. Refund Ticket", () => {
+    it("should refund the scheduled share and put the seat back on sale", async () => {
+      const refunded = 5; // Unregistered, row 2 seat 4, still in the custodial wallet
+      const leaf = soldLeaves[refunded];
+      const vaultPda = findEventVaultPda(eventPda);
+
+      const eventBefore = await program.account.event.fetch(eventPda);
+      const tierBefore = await program.account.ticketTier.fetch(findTierPda(0));
+      const seatMapBefore = await program.account.seatMap.fetch(findSeatMapPda("A"));
+      const vaultBefore = await program.account.eventVault.fetch(vaultPda);
+      const platformBefore = await program.account.platform.fetch(platformPda);
+      const vaultLamportsBefore = balanceOf(svm, vaultPda);
+      const seatIndex = (leaf.row - 1) * seatMapBefore.seatsPerRow + (leaf.seat - 1);
+      assert.notEqual(seatMapBefore.sold[seatIndex >> 3] & (1 << (seatIndex & 7)), 0);
+
+      const { leafArgs, proofAccounts } = leafProof(refunded);
+      await program.methods
+        .refundTicket(leafArgs, metadataArg(leaf.metadata))
+        .accounts(refundAccounts(refunded, authority.publicKey, null))
+        .remainingAccounts(proofAccounts)
+        .rpc();
+      eventTrees[leaf.treeIndex].setOwner(leaf, null);
+
+      // Inside a week of the show the schedule pays back half, and the
+      // platform gives up the same half of its fee
+      const amount = ticketPrice.toNumber() / 2;
+      const feeRefunded = Math.floor((ticketPrice.toNumber() * 250) / 10000 / 2);
+      assert.equal(balanceOf(svm, vaultPda), vaultLamportsBefore - amount);
+
+      const vault = await program.account.eventVault.fetch(vaultPda);
+      assert.equal(vault.refunded.sub(vaultBefore.refunded).toNumber(), amount);
+      assert.equal(vault.feesRefunded.sub(vaultBefore.feesRefunded).toNumber(), feeRefunded);
+      assert.equal(vault.grossSales.toString(), vaultBefore.grossSales.toString());
+
+      const platformAccount = await program.account.platform.fetch(platformPda);
+      assert.equal(platformAccount.totalTicketsRefunded.sub(platformBefore.totalTicketsRefunded).toNumber(), 1);
+      assert.equal(platformAccount.totalRefunded.sub(platformBefore.totalRefunded).toNumber(), amount);
+      assert.equal(
+        platformBefore.totalFeesCollected.sub(platformAccount.totalFeesCollected).toNumber(),
+        feeRefunded
+      );
+
+      // The ticket and its seat can be sold again
+      const eventAccount = await program.account.event.fetch(eventPda);
+      const tier = await program.account.ticketTier.fetch(findTierPda(0));
+      const seatMap = await program.account.seatMap.fetch(findSeatMapPda("A"));
+      assert.equal(eventAccount.ticketsSold, eventBefore.ticketsSold - 1);
+      assert.equal(tier.sold, tierBefore.sold - 1);
+      assert.equal(seatMap.seatsSold, seatMapBefore.seatsSold - 1);
+      assert.equal(seatMap.sold[seatIndex >> 3] & (1 << (seatIndex & 7)), 0);
+
+      const eventTree = await program.account.eventTree.fetch(findEventTreePda(leaf.treeIndex));
+      assert.notEqual(eventTree.refunded[leaf.nonce >> 3] & (1 << (leaf.nonce & 7)), 0);
+
+      console.log("✓ Refunded", amount, "lamports and released seat", `${leaf.row}-${leaf.seat}`);
+    });
+
+    it("should reject a second refund of the same ticket", async () => {
+      const refunded = 5;
+      const leaf = soldLeaves[refunded];
+      // The leaf was burned, so its old contents no longer prove anything
+      const { leafArgs, proofAccounts } = leafProof(refunded);
+
+      try {
+        await program.methods
+          .refundTicket(leafArgs, metadataArg(leaf.metadata))
+          .accounts(refundAccounts(refunded, authority.publicKey, null))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for a repeated refund");
+      } catch (error) {
+        assert.include(error.toString(), "ConcurrentMerkleTreeError");
+        console.log("✓ Correctly rejected a repeated refund");
+      }
+    });
+
+    it("should reject a refund of a ticket already scanned", async () => {
+      const scanned = ticketId.toNumber();
+      const leaf = soldLeaves[scanned];
+      const { leafArgs, proofAccounts } = leafProof(scanned);
+
+      try {
+        await program.methods
+          .refundTicket(leafArgs, metadataArg(leaf.metadata))
+          .accounts(refundAccounts(scanned, authority.publicKey, authority.publicKey))
+          .remainingAccounts(proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for refunding a used ticket");
+      } catch (error) {
+        assert.include(error.toString(), "TicketAlreadyUsed");
+        console.log("✓ Correctly rejected a refund after check-in");
+      }
+    });
+  });
+
+  describe("11. Event Settlement", () => {
+    // Venue share goes to the owner until set_venue_payout is called
+    const venuePayoutAccounts = [{ pubkey: authority.publicKey, isWritable: true, isSigner: false }];
+    const settleAccounts = () => ({
+      authority: authority.publicKey,
+      platform: platformPda,
+      venue: venuePda,
+      event: eventPda,
+      eventVault: findEventVaultPda(eventPda),
+      settlement: findSettlementPda(eventPda),
+      treasury: treasury.publicKey,
+      artistWallet: artistWallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    });
+
+    it("should reject settlement before the event has ended", async () => {
+      try {
+        await program.methods.settleEvent().accounts(settleAccounts()).remainingAccounts(venuePayoutAccounts).rpc();
+
+        assert.fail("Should have thrown an error for settling early");
+      } catch (error) {
+        assert.include(error.toString(), "EventNotEnded");
+        console.log("✓ Correctly rejected early settlement");
+      }
+    });
+
+    it("should only let the platform place a dispute hold", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .setDisputeHold(true)
+          .accounts({ authority: stranger.publicKey, platform: platformPda, roleRegistry: roleRegistryPda, event: eventPda })
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for unauthorized hold");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+
+      await program.methods
+        .setDisputeHold(true)
+        .accounts({ authority: authority.publicKey, platform: platformPda, roleRegistry: roleRegistryPda, event: eventPda })
+        .rpc();
+      assert.equal((await program.account.event.fetch(eventPda)).disputeHold, true);
+
+      try {
+        await program.methods.settleEvent().accounts(settleAccounts()).remainingAccounts(venuePayoutAccounts).rpc();
+        assert.fail("Should have thrown an error for settling on hold");
+      } catch (error) {
+        assert.include(error.toString(), "SettlementOnHold");
+      }
+
+      await program.methods
+        .setDisputeHold(false)
+        .accounts({ authority: authority.publicKey, platform: platformPda, roleRegistry: roleRegistryPda, event: eventPda })
+        .rpc();
+      console.log("✓ Dispute hold placed and released by the platform");
+    });
+  });
+
+  describe("12. Platform Pause", () => {
+    const pauser = Keypair.generate();
+
+    it("should reject pausing by an unrelated signer", async () => {
+      try {
+        await program.methods
+          .pausePlatform()
+          .accounts({ authority: pauser.publicKey, platform: platformPda, roleRegistry: roleRegistryPda })
+          .signers([pauser])
+          .rpc();
+
+        assert.fail("Should have thrown an error for unauthorized pause");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+    });
+
+    it("should let a key with the pauser role stop state changes", async () => {
+      await program.methods
+        .grantRole(pauser.publicKey, { pauser: {} })
+        .accounts({ owner: authority.publicKey, platform: platformPda, roleRegistry: roleRegistryPda })
+        .rpc();
+
+      await program.methods
+        .pausePlatform()
+        .accounts({ authority: pauser.publicKey, platform: platformPda, roleRegistry: roleRegistryPda })
+        .signers([pauser])
+        .rpc();
+      assert.equal((await program.account.platform.fetch(platformPda)).paused, true);
+
+      const [pausedVenuePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("venue"), Buffer.from("paused-venue")],
+        program.programId
+      );
+      try {
+        await program.methods
+          .createVenue("paused-venue", venueName, venueMetadataUri)
+          .accounts({
+            owner: authority.publicKey,
+            platform: platformPda,
+            venue: pausedVenuePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error while paused");
+      } catch (error) {
+        assert.include(error.toString(), "PlatformPaused");
+      }
+
+      await program.methods
+        .unpausePlatform()
+        .accounts({ authority: authority.publicKey, platform: platformPda, roleRegistry: roleRegistryPda })
+        .rpc();
+      assert.equal((await program.account.platform.fetch(platformPda)).paused, false);
+
+      await program.methods
+        .revokeRole(pauser.publicKey, { pauser: {} })
+        .accounts({ owner: authority.publicKey, platform: platformPda, roleRegistry: roleRegistryPda })
+        .rpc();
+      const registry = await program.account.roleRegistry.fetch(roleRegistryPda);
+      assert.equal(registry.members.length, 0);
+
+      console.log("✓ Pause blocked venue creation until unpaused");
+    });
+  });
+
+  describe("13. Platform Administration", () => {
+    it("should reject a default treasury and rotate to a new one", async () => {
+      try {
+        await program.methods
+          .updateTreasury(PublicKey.default)
+          .accounts({ owner: authority.publicKey, platform: platformPda })
+          .rpc();
+
+        assert.fail("Should have thrown an error for invalid treasury");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidTreasury");
+      }
+
+      const newTreasury = Keypair.generate();
+      await program.methods
+        .updateTreasury(newTreasury.publicKey)
+        .accounts({ owner: authority.publicKey, platform: platformPda })
+        .rpc();
+      let platformAccount = await program.account.platform.fetch(platformPda);
+      assert.ok(platformAccount.treasury.equals(newTreasury.publicKey));
+
+      await program.methods
+        .updateTreasury(treasury.publicKey)
+        .accounts({ owner: authority.publicKey, platform: platformPda })
+        .rpc();
+      platformAccount = await program.account.platform.fetch(platformPda);
+      assert.ok(platformAccount.treasury.equals(treasury.publicKey));
+    });
+
+    it("should hand over ownership only once the new owner accepts", async () => {
+      const newOwner = Keypair.generate();
+      const stranger = Keypair.generate();
+
+      await program.methods
+        .proposePlatformOwner(newOwner.publicKey)
+        .accounts({ owner: authority.publicKey, platform: platformPda })
+        .rpc();
+      await program.methods
+        .cancelPlatformOwnerTransfer()
+        .accounts({ owner: authority.publicKey, platform: platformPda })
+        .rpc();
+      let platformAccount = await program.account.platform.fetch(platformPda);
+      assert.ok(platformAccount.pendingOwner.equals(PublicKey.default));
+
+      await program.methods
+        .proposePlatformOwner(newOwner.publicKey)
+        .accounts({ owner: authority.publicKey, platform: platformPda })
+        .rpc();
+      try {
+        await program.methods
+          .acceptPlatformOwner()
+          .accounts({ newOwner: stranger.publicKey, platform: platformPda })
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for the wrong acceptor");
+      } catch (error) {
+        assert.include(error.toString(), "NotPendingOwner");
+      }
+
+      await program.methods
+        .acceptPlatformOwner()
+        .accounts({ newOwner: newOwner.publicKey, platform: platformPda })
+        .signers([newOwner])
+        .rpc();
+      platformAccount = await program.account.platform.fetch(platformPda);
+      assert.ok(platformAccount.owner.equals(newOwner.publicKey));
+
+      // Hand it back so later tests keep using the provider wallet
+      await program.methods
+        .proposePlatformOwner(authority.publicKey)
+        .accounts({ owner: newOwner.publicKey, platform: platformPda })
+        .signers([newOwner])
+        .rpc();
+      await program.methods
+        .acceptPlatformOwner()
+        .accounts({ newOwner: authority.publicKey, platform: platformPda })
+        .rpc();
+      platformAccount = await program.account.platform.fetch(platformPda);
+      assert.ok(platformAccount.owner.equals(authority.publicKey));
+
+      console.log("✓ Two-step ownership transfer round-tripped");
+    });
+  });
+
+  describe("14. Admin Timelock", () => {
+    const findAdminOperationPda = (id: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("admin_op"), id.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    it("should queue a fee change behind the timelock and allow cancelling it", async () => {
+      const { nextOperationId } = await program.account.platform.fetch(platformPda);
+      const operationPda = findAdminOperationPda(nextOperationId);
+
+      await program.methods
+        .queueAdminOperation({ setFeeBps: { feeBps: 300 } })
+        .accounts({
+          authority: authority.publicKey,
+          platform: platformPda,
+          roleRegistry: roleRegistryPda,
+          adminOperation: operationPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const operation = await program.account.adminOperation.fetch(operationPda);
+      assert.ok(operation.executableAt.gt(operation.proposedAt));
+
+      try {
+        await program.methods
+          .executeAdminOperation()
+          .accounts({ authority: authority.publicKey, platform: platformPda, adminOperation: operationPda })
+          .rpc();
+
+        assert.fail("Should have thrown an error for a timelocked operation");
+      } catch (error) {
+        assert.include(error.toString(), "OperationNotReady");
+      }
+
+      await program.methods
+        .cancelAdminOperation()
+        .accounts({ authority: authority.publicKey, platform: platformPda, adminOperation: operationPda })
+        .rpc();
+      assert.equal((await program.account.adminOperation.fetch(operationPda)).cancelled, true);
+
+      // The fee on the platform and on existing events is untouched
+      assert.equal((await program.account.platform.fetch(platformPda)).feeBps, 250);
+      assert.equal((await program.account.event.fetch(eventPda)).platformFeeBps, 250);
+      console.log("✓ Fee change queued and cancelled before it took effect");
+    });
+
+    it("should reject fee changes above the cap", async () => {
+      const { nextOperationId } = await program.account.platform.fetch(platformPda);
+      try {
+        await program.methods
+          .queueAdminOperation({ setFeeBps: { feeBps: 1001 } })
+          .accounts({
+            authority: authority.publicKey,
+            platform: platformPda,
+            roleRegistry: roleRegistryPda,
+            adminOperation: findAdminOperationPda(nextOperationId),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error for fee above cap");
+      } catch (error) {
+        assert.include(error.toString(), "FeeTooHigh");
+      }
+    });
+  });
+
+  describe("15. Platform Stats", () => {
+    it("should return the platform counters without a transaction", async () => {
+      const stats = await program.methods
+        .getPlatformStats()
+        .accounts({ platform: platformPda })
+        .view();
+
+      const platformAccount = await program.account.platform.fetch(platformPda);
+      assert.equal(stats.totalVenues.toNumber(), platformAccount.totalVenues.toNumber());
+      assert.isAtLeast(stats.totalEvents.toNumber(), 1);
+      assert.isAtLeast(stats.totalTicketsSold.toNumber(), 1);
+      assert.ok(stats.totalFeesCollected.gtn(0));
+      assert.equal(stats.totalResales.toNumber(), 1);
+
+      console.log("✓ Platform stats:", stats.totalTicketsSold.toString(), "tickets sold");
+    });
+  });
+
+  describe("16. Venue Lifecycle", () => {
+    it("should let the venue owner update its profile", async () => {
+      await program.methods
+        .updateVenue("Renamed Venue", "https://example.com/venue-v2.json")
+        .accounts({ owner: authority.publicKey, platform: platformPda, venue: venuePda })
+        .rpc();
+
+      const venueAccount = await program.account.venue.fetch(venuePda);
+      const name = Buffer.from(venueAccount.name).toString().replace(/\0/g, "");
+      assert.equal(name, "Renamed Venue");
+    });
+
+    it("should deactivate and reactivate the venue", async () => {
+      const accounts = {
+        authority: authority.publicKey,
+        platform: platformPda,
+        roleRegistry: roleRegistryPda,
+        venue: venuePda,
+      };
+
+      await program.methods.setVenueActive(false).accounts(accounts).rpc();
+      let venueAccount = await program.account.venue.fetch(venuePda);
+      assert.equal(venueAccount.active, false);
+      assert.ok(venueAccount.deactivatedBy.equals(authority.publicKey));
+
+      await program.methods.setVenueActive(true).accounts(accounts).rpc();
+      venueAccount = await program.account.venue.fetch(venuePda);
+      assert.equal(venueAccount.active, true);
+      assert.ok(venueAccount.deactivatedBy.equals(PublicKey.default));
+    });
+
+    it("should revoke verification with a reason and allow re-verification", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .revokeVenueVerification(7)
+          .accounts({
+            authority: stranger.publicKey,
+            platform: platformPda,
+            roleRegistry: roleRegistryPda,
+            venue: venuePda,
+          })
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for unauthorized revocation");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+      }
+
+      await program.methods
+        .revokeVenueVerification(7)
+        .accounts({
+          authority: authority.publicKey,
+          platform: platformPda,
+          roleRegistry: roleRegistryPda,
+          venue: venuePda,
+        })
+        .rpc();
+      let venueAccount = await program.account.venue.fetch(venuePda);
+      assert.equal(venueAccount.verified, false);
+      assert.equal(venueAccount.revocationReason, 7);
+
+      await program.methods
+        .verifyVenue()
+        .accounts({
+          authority: authority.publicKey,
+          platform: platformPda,
+          roleRegistry: roleRegistryPda,
+          venue: venuePda,
+        })
+        .rpc();
+      venueAccount = await program.account.venue.fetch(venuePda);
+      assert.equal(venueAccount.verified, true);
+      assert.equal(venueAccount.revocationReason, 0);
+
+      console.log("✓ Venue verification revoked and restored");
+    });
+
+    it("should only accept payout weights that add up to 100%", async () => {
+      const promoter = Keypair.generate().publicKey;
+      const accounts = { owner: authority.publicKey, platform: platformPda, venue: venuePda };
+
+      try {
+        await program.methods
+          .setVenuePayout([
+            { wallet: authority.publicKey, weightBps: 7000 },
+            { wallet: promoter, weightBps: 2000 },
+          ])
+          .accounts(accounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for weights under 100%");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidPayoutWallets");
+      }
+
+      await program.methods
+        .setVenuePayout([
+          { wallet: authority.publicKey, weightBps: 7000 },
+          { wallet: promoter, weightBps: 3000 },
+        ])
+        .accounts(accounts)
+        .rpc();
+
+      const venueAccount = await program.account.venue.fetch(venuePda);
+      assert.equal(venueAccount.payoutCount, 2);
+      assert.ok(venueAccount.payoutWallets[1].wallet.equals(promoter));
+      assert.equal(venueAccount.payoutWallets[1].weightBps, 3000);
+      console.log("✓ Venue payout split 70/30 with a promoter");
+    });
+  });
+
+  describe("17. Venue Staff", () => {
+    const scanner = Keypair.generate();
+    const SCAN_TICKETS = 1 << 2;
+
+    const findVenueStaffPda = (staff: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("venue_staff"), venuePda.toBuffer(), staff.toBuffer()],
+        program.programId
+      )[0];
+
+    it("should reject empty or unknown permission flags", async () => {
+      for (const permissions of [0, 1 << 5]) {
+        try {
+          await program.methods
+            .addVenueStaff(scanner.publicKey, permissions, new anchor.BN(0))
+            .accounts({
+              owner: authority.publicKey,
+              platform: platformPda,
+              venue: venuePda,
+              venueStaff: findVenueStaffPda(scanner.publicKey),
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+          assert.fail("Should have thrown an error for invalid permissions");
+        } catch (error) {
+          assert.include(error.toString(), "InvalidStaffPermissions");
+        }
+      }
+      console.log("✓ Correctly rejected invalid staff permissions");
+    });
+
+    it("should add a scanner that cannot edit events", async () => {
+      await program.methods
+        .addVenueStaff(scanner.publicKey, SCAN_TICKETS, new anchor.BN(0))
+        .accounts({
+          owner: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          venueStaff: findVenueStaffPda(scanner.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const staffAccount = await program.account.venueStaff.fetch(findVenueStaffPda(scanner.publicKey));
+      assert.ok(staffAccount.staff.equals(scanner.publicKey));
+      assert.equal(staffAccount.permissions, SCAN_TICKETS);
+      assert.equal(staffAccount.expiresAt.toNumber(), 0);
+
+      const [seatMapPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("seat_map"), eventPda.toBuffer(), Buffer.from("B")],
+        program.programId
+      );
+      try {
+        await program.methods
+          .initializeSeatMap("B", 5, 10, 0b1)
+          .accounts({
+            authority: scanner.publicKey,
+            platform: platformPda,
+            venue: venuePda,
+            venueStaff: findVenueStaffPda(scanner.publicKey),
+            event: eventPda,
+            seatMap: seatMapPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([scanner])
+          .rpc();
+
+        assert.fail("Should have thrown an error for a scanner editing the event");
+      } catch (error) {
+        assert.include(error.toString(), "UnauthorizedVenue");
+      }
+      console.log("✓ Scanner added with SCAN_TICKETS only");
+    });
+
+    it("should let venue staff move the check-in window but not past a day", async () => {
+      const accounts = {
+        authority: authority.publicKey,
+        platform: platformPda,
+        venue: venuePda,
+        venueStaff: null,
+        event: eventPda,
+      };
+
+      try {
+        await program.methods
+          .setCheckinWindow(new anchor.BN(25 * 3600), new anchor.BN(3600))
+          .accounts(accounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for an out of range offset");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidCheckInWindow");
+      }
+
+      // Festival gates open four hours early
+      await program.methods
+        .setCheckinWindow(new anchor.BN(4 * 3600), new anchor.BN(3600))
+        .accounts(accounts)
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      assert.equal(eventAccount.doorsOpen.toNumber(), 4 * 3600);
+      assert.equal(eventAccount.checkinClose.toNumber(), 3600);
+      console.log("✓ Check-in window moved to open 4 hours early");
+    });
+
+    it("should let the owner remove staff", async () => {
+      await program.methods
+        .removeVenueStaff()
+        .accounts({
+          owner: authority.publicKey,
+          venue: venuePda,
+          venueStaff: findVenueStaffPda(scanner.publicKey),
+        })
+        .rpc();
+
+      assert.ok(isClosed(svm, findVenueStaffPda(scanner.publicKey)));
+      console.log("✓ Scanner removed");
+    });
+  });
+
+  describe("18. Event Cancellation", () => {
+    it("should reject cancellation by an unrelated signer", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .cancelEvent()
+          .accounts({
+            authority: stranger.publicKey,
+            platform: platformPda,
+            venue: venuePda,
+            event: eventPda,
+          })
+          .signers([stranger])
+          .rpc();
+
+        assert.fail("Should have thrown an error for unauthorized cancellation");
+      } catch (error) {
+        assert.include(error.toString(), "Unauthorized");
+        console.log("✓ Correctly rejected unauthorized cancellation");
+      }
+    });
+
+    it("should cancel the event and keep proceeds in the vault", async () => {
+      const vaultBefore = balanceOf(svm, findEventVaultPda(eventPda));
+
+      await program.methods
+        .cancelEvent()
+        .accounts({
+          authority: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          event: eventPda,
+        })
+        .rpc();
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      const platformAccount = await program.account.platform.fetch(platformPda);
+      assert.equal(eventAccount.cancelled, true);
+      assert.equal(eventAccount.refundFeeOnCancel, platformAccount.refundFeeOnCancel);
+
+      // Proceeds stay escrowed for claim_refund
+      const vault = await program.account.eventVault.fetch(findEventVaultPda(eventPda));
+      assert.equal(vault.paidOut.toNumber(), 0);
+      assert.equal(balanceOf(svm, findEventVaultPda(eventPda)), vaultBefore);
+
+      console.log("✓ Event cancelled with", vault.grossSales.toString(), "lamports escrowed");
+    });
+
+    it("should not admit tickets to a cancelled event", async () => {
+      try {
+        await program.methods
+          .verifyTicket("Main Gate")
+          .accounts({
+            validator: authority.publicKey,
+            platform: platformPda,
+            venue: venuePda,
+            venueStaff: null,
+            holder: null,
+            event: eventPda,
+            ticket: findTicketPda(eventPda, new anchor.BN(0)),
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error for scanning a cancelled event");
+      } catch (error) {
+        assert.include(error.toString(), "EventCancelled");
+        console.log("✓ Correctly rejected check-in for a cancelled event");
+      }
+    });
+
+    it("should burn the ticket and pay the full price back from the vault", async () => {
+      const claimed = 0; // Registered at purchase, still in the custodial wallet
+      const leaf = soldLeaves[claimed];
+      const vaultPda = findEventVaultPda(eventPda);
+      const claimedPda = findTicketPda(eventPda, new anchor.BN(claimed));
+      // Claims take the same accounts as a refund, without the seat map
+      const { seatMap, ...claimAccounts } = refundAccounts(claimed, authority.publicKey, authority.publicKey);
+
+      const eventAccount = await program.account.event.fetch(eventPda);
+      const vaultBefore = await program.account.eventVault.fetch(vaultPda);
+      const platformBefore = await program.account.platform.fetch(platformPda);
+      const vaultLamportsBefore = balanceOf(svm, vaultPda);
+
+      const { leafArgs, proofAccounts } = leafProof(claimed);
+      await program.methods
+        .claimRefund(leafArgs, metadataArg(leaf.metadata))
+        .accounts(claimAccounts)
+        .remainingAccounts(proofAccounts)
+        .rpc();
+      eventTrees[leaf.treeIndex].setOwner(leaf, null);
+
+      // The platform keeps its fee unless the cancel policy hands it back
+      const price = ticketPrice.toNumber();
+      const fee = Math.floor((price * 250) / 10000);
+      const amount = eventAccount.refundFeeOnCancel ? price : price - fee;
+      const feeRefunded = eventAccount.refundFeeOnCancel ? fee : 0;
+      assert.equal(balanceOf(svm, vaultPda), vaultLamportsBefore - amount);
+
+      const vault = await program.account.eventVault.fetch(vaultPda);
+      assert.equal(vault.refunded.sub(vaultBefore.refunded).toNumber(), amount);
+      assert.equal(vault.feesRefunded.sub(vaultBefore.feesRefunded).toNumber(), feeRefunded);
+
+      const platformAccount = await program.account.platform.fetch(platformPda);
+      assert.equal(platformAccount.totalTicketsRefunded.sub(platformBefore.totalTicketsRefunded).toNumber(), 1);
+      assert.equal(platformAccount.totalRefunded.sub(platformBefore.totalRefunded).toNumber(), amount);
+
+      // The leaf is marked refunded and the registry PDA closed
+      const eventTree = await program.account.eventTree.fetch(findEventTreePda(leaf.treeIndex));
+      assert.notEqual(eventTree.refunded[leaf.nonce >> 3] & (1 << (leaf.nonce & 7)), 0);
+      assert.ok(isClosed(svm, claimedPda));
+
+      // A second claim on the burned leaf fails
+      const burned = leafProof(claimed);
+      try {
+        await program.methods
+          .claimRefund(burned.leafArgs, metadataArg(leaf.metadata))
+          .accounts(claimAccounts)
+          .remainingAccounts(burned.proofAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error for a repeated claim");
+      } catch (error) {
+        assert.include(error.toString(), "ConcurrentMerkleTreeError");
+      }
+
+      console.log("✓ Claimed", amount, "lamports for a cancelled ticket");
+    });
+  });
+
+  describe("19. Event Settlement Payout", () => {
+    // A second event that runs to completion, since the first was cancelled
+    const encoreId = new anchor.BN(2);
+    const encoreArtist = Keypair.generate();
+    const encoreTree = Keypair.generate();
+    const quantity = 3;
+    let encorePda: PublicKey;
+
+    const findEncorePda = (seed: string) =>
+      PublicKey.findProgramAddressSync([Buffer.from(seed), encorePda.toBuffer()], program.programId)[0];
+
+    before(async () => {
+      [encorePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("event"), venuePda.toBuffer(), encoreId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const now = unixNow(svm);
+      const staffAccounts = {
+        authority: authority.publicKey,
+        platform: platformPda,
+        venue: venuePda,
+        venueStaff: null,
+        event: encorePda,
+      };
+
+      await program.methods
+        .createEvent({
+          eventId: encoreId,
+          name: "Encore",
+          ticketPrice,
+          totalTickets: 8,
+          startTime: new anchor.BN(now + 3601),
+          endTime: new anchor.BN(now + 7200),
+          refundWindow: new anchor.BN(0),
+          doorsOpen: new anchor.BN(3600),
+          checkinClose: new anchor.BN(3600),
+          selfCustody: false,
+          refundSchedule: [],
+          metadataUri: "https://example.com/encore-metadata.json",
+          oracleFeed: PublicKey.default,
+          description: "The second night",
+          transferable: true,
+          resaleable: true,
+          artistWallet: encoreArtist.publicKey,
+          artistPercentage,
+          venuePercentage,
+        })
+        .accounts({
+          ...staffAccounts,
+          reentrancyGuard: findEncorePda("reentrancy"),
+          eventVault: findEventVaultPda(encorePda),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .createTicketTier({
+          name: "GA",
+          price: ticketPrice,
+          capacity: 8,
+          saleStart: new anchor.BN(0),
+          saleEnd: new anchor.BN(0),
+        })
+        .accounts({ ...staffAccounts, tier: findTierPda(0, encorePda), systemProgram: SystemProgram.programId })
+        .rpc();
+
+      await program.methods
+        .initializeSeatMap("A", 1, 8, 0b1)
+        .accounts({ ...staffAccounts, seatMap: findSeatMapPda("A", encorePda), systemProgram: SystemProgram.programId })
+        .rpc();
+
+      await program.methods
+        .initializeEventTree(8, 96)
+        .accounts({
+          ...staffAccounts,
+          eventTree: findEventTreePda(0, encorePda),
+          treeConfig: findTreeConfigPda(encoreTree.publicKey),
+          merkleTree: encoreTree.publicKey,
+          treeCreator: findEncorePda("tree_creator"),
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([encoreTree])
+        .rpc();
+
+      const collectionAuthority = findEncorePda("collection_authority");
+      const collectionMint = findEncorePda("collection_mint");
+      await program.methods
+        .createEventCollection()
+        .accounts({
+          ...staffAccounts,
+          collectionAuthority,
+          collectionMint,
+          collectionTokenAccount: findAssociatedTokenAddress(collectionAuthority, collectionMint),
+          collectionMetadata: findMetadataPda(collectionMint),
+          collectionEdition: findMasterEditionPda(collectionMint),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+      await program.methods
+        .purchaseTickets({ tierIndex: 0, quantity, section: "A", row: 1, seatStart: 1, ownerCommitments: [] })
+        .accounts({
+          buyer: authority.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          event: encorePda,
+          reentrancyGuard: findEncorePda("reentrancy"),
+          eventVault: findEventVaultPda(encorePda),
+          tier: findTierPda(0, encorePda),
+          seatMap: findSeatMapPda("A", encorePda),
+          eventTree: findEventTreePda(0, encorePda),
+          treeConfig: findTreeConfigPda(encoreTree.publicKey),
+          merkleTree: encoreTree.publicKey,
+          nextEventTree: null,
+          nextTreeConfig: null,
+          nextMerkleTree: null,
+          treeCreator: findEncorePda("tree_creator"),
+          collectionAuthority,
+          collectionMint,
+          collectionMetadata: findMetadataPda(collectionMint),
+          collectionEdition: findMasterEditionPda(collectionMint),
+          bubblegumSigner: findBubblegumSignerPda(),
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("should pay the platform its fees, the artist its share and the venue wallets the rest", async () => {
+      // Anyone may settle; a third party pays the fees so the payees'
+      // balances move by exactly their share
+      const settler = Keypair.generate();
+      svm.airdrop(settler.publicKey, BigInt(anchor.web3.LAMPORTS_PER_SOL));
+      const settlerProgram = new Program(idl as anchor.Idl, new LiteSVMProvider(svm, new anchor.Wallet(settler)));
+
+      const vaultPda = findEventVaultPda(encorePda);
+      const venueAccount = await program.account.venue.fetch(venuePda);
+      const payoutWallets = venueAccount.payoutWallets.slice(0, venueAccount.payoutCount);
+      const payees = [treasury.publicKey, encoreArtist.publicKey, ...payoutWallets.map((payout) => payout.wallet)];
+      const balancesBefore = payees.map((payee) => balanceOf(svm, payee));
+      const vaultLamportsBefore = balanceOf(svm, vaultPda);
+
+      // The show is over and there is no refund window to wait out
+      warp(svm, 7200);
+
+      await settlerProgram.methods
+        .settleEvent()
+        .accounts({
+          authority: settler.publicKey,
+          platform: platformPda,
+          venue: venuePda,
+          event: encorePda,
+          eventVault: vaultPda,
+          settlement: findSettlementPda(encorePda),
+          treasury: treasury.publicKey,
+          artistWallet: encoreArtist.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(payoutWallets.map((payout) => ({ pubkey: payout.wallet, isWritable: true, isSigner: false })))
+        .rpc();
+
+      // Platform fees first, the artist's cut of the net, then the venue's
+      // weighted wallets with any rounding dust on the first
+      const gross = ticketPrice.toNumber() * quantity;
+      const feesHeld = Math.floor((ticketPrice.toNumber() * 250) / 10000) * quantity;
+      const artistShare = Math.floor(((gross - feesHeld) * artistPercentage) / 10000);
+      const venueShare = gross - feesHeld - artistShare;
+      const walletShares = payoutWallets.map((payout) => Math.floor((venueShare * payout.weightBps) / 10000));
+      walletShares[0] += venueShare - walletShares.reduce((sum, share) => sum + share, 0);
+
+      const expected = [feesHeld, artistShare, ...walletShares];
+      payees.forEach((payee, i) => assert.equal(balanceOf(svm, payee) - balancesBefore[i], expected[i]));
+      assert.equal(vaultLamportsBefore - balanceOf(svm, vaultPda), gross);
+
+      const vault = await program.account.eventVault.fetch(vaultPda);
+      assert.equal(vault.paidOut.toNumber(), gross);
+
+      const settlement = await program.account.settlement.fetch(findSettlementPda(encorePda));
+      assert.ok(settlement.event.equals(encorePda));
+      assert.equal(settlement.grossSales.toNumber(), gross);
+      assert.equal(settlement.refunds.toNumber(), 0);
+      assert.equal(settlement.platformPayout.toNumber(), feesHeld);
+      assert.equal(settlement.artistPayout.toNumber(), artistShare);
+      assert.equal(settlement.venuePayout.toNumber(), venueShare);
+      assert.equal(settlement.ticketsSold, quantity);
+      assert.ok(settlement.settledBy.equals(settler.publicKey));
+      assert.equal((await program.account.event.fetch(encorePda)).settled, true);
+
+      console.log("✓ Settled", gross, "lamports: venue", venueShare, "/ artist", artistShare, "/ platform", feesHeld);
     });
   });
 });
@@ -0,0 +1,188 @@
+import { AccountMeta, PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
+import {
+  Creator,
+  MetadataArgs,
+  TokenProgramVersion,
+  TokenStandard,
+  UseMethod,
+  computeCompressedNFTHash,
+} from "@metaplex-foundation/mpl-bubblegum";
+import { MerkleTree } from "@solana/spl-account-compression";
+import { LiteSVM } from "litesvm";
+
+// Program IDs loaded into the test SVM from tests/fixtures
+export const BUBBLEGUM_PROGRAM_ID = new PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+export const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+export const SPL_NOOP_PROGRAM_ID = new PublicKey("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+export const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+export const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+export function findTreeConfigPda(merkleTree: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([merkleTree.toBuffer()], BUBBLEGUM_PROGRAM_ID)[0];
+}
+
+// Asset ID of the leaf minted with `nonce`, matching mpl_bubblegum::utils::get_asset_id
+export function findAssetId(merkleTree: PublicKey, nonce: number): PublicKey {
+  const nonceBytes = Buffer.alloc(8);
+  nonceBytes.writeBigUInt64LE(BigInt(nonce));
+  return PublicKey.findProgramAddressSync([Buffer.from("asset"), merkleTree.toBuffer(), nonceBytes], BUBBLEGUM_PROGRAM_ID)[0];
+}
+
+export function findBubblegumSignerPda(): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("collection_cpi")], BUBBLEGUM_PROGRAM_ID)[0];
+}
+
+export function findMetadataPda(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+export function findMasterEditionPda(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from("edition")],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+export function findAssociatedTokenAddress(owner: PublicKey, mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  )[0];
+}
+
+// Mirrors getConcurrentMerkleTreeAccountSize from @solana/spl-account-compression
+export function merkleTreeAccountSize(maxDepth: number, maxBufferSize: number, canopyDepth = 0): number {
+  const header = 2 + 54;
+  const changeLog = 32 + 32 * maxDepth + 4 + 4;
+  const rightmostPath = 32 * maxDepth + 32 + 4 + 4;
+  const tree = 8 + 8 + 8 + maxBufferSize * changeLog + rightmostPath;
+  const canopy = canopyDepth > 0 ? ((1 << (canopyDepth + 1)) - 2) * 32 : 0;
+  return header + tree + canopy;
+}
+
+export function fetchNumMinted(svm: LiteSVM, treeConfig: PublicKey): number {
+  const info = svm.getAccount(treeConfig);
+  if (!info) {
+    throw new Error(`Tree config ${treeConfig.toBase58()} not found`);
+  }
+  // discriminator (8) + tree_creator (32) + tree_delegate (32) + total_mint_capacity (8)
+  return Number(Buffer.from(info.data).readBigUInt64LE(80));
+}
+
+// Mirrors utils::royalties::calculate_royalties for (address, bps) parties in creator order
+export function ticketRoyalties(parties: [PublicKey, number][]): { sellerFeeBasisPoints: number; creators: Creator[] } {
+  const paid = parties.filter(([, bps]) => bps > 0);
+  const totalBps = paid.reduce((sum, [, bps]) => sum + bps, 0);
+  const creators = paid.map(([address, bps]) => ({ address, verified: false, share: Math.floor((bps * 100) / totalBps) }));
+  let largest = 0;
+  paid.forEach(([, bps], i) => {
+    if (bps > paid[largest][1]) largest = i;
+  });
+  if (creators.length > 0) {
+    creators[largest].share += 100 - creators.reduce((sum, creator) => sum + creator.share, 0);
+  }
+  return { sellerFeeBasisPoints: totalBps, creators };
+}
+
+export interface TicketSale {
+  eventName: string;
+  metadataUri: string;
+  tierName: string;
+  tierIndex: number;
+  collectionMint: PublicKey;
+  royalties: { sellerFeeBasisPoints: number; creators: Creator[] };
+}
+
+export interface TicketLeaf {
+  treeIndex: number;
+  tierIndex: number;
+  ticketNumber: number;
+  section: string;
+  row: number;
+  seat: number;
+  nonce: number;
+  metadata: MetadataArgs;
+}
+
+// Mirrors utils::merkle::create_ticket_metadata, as stored in the leaf once Bubblegum verifies the collection
+export function ticketLeaf(
+  sale: TicketSale,
+  treeIndex: number,
+  nonce: number,
+  ticketNumber: number,
+  section: string,
+  row: number,
+  seat: number
+): TicketLeaf {
+  const name = `${sale.tierName} #${ticketNumber} - ${sale.eventName}`.slice(0, 32);
+  const uri = `${sale.metadataUri}/tickets/${ticketNumber}?tier=${sale.tierIndex}&section=${section}&row=${row}&seat=${seat}`;
+  const metadata: MetadataArgs = {
+    name,
+    symbol: "TKT",
+    uri: uri.slice(0, 200),
+    sellerFeeBasisPoints: sale.royalties.sellerFeeBasisPoints,
+    primarySaleHappened: true,
+    isMutable: false,
+    editionNonce: null,
+    tokenStandard: TokenStandard.NonFungible,
+    collection: { verified: true, key: sale.collectionMint },
+    uses: { useMethod: UseMethod.Single, remaining: 1, total: 1 },
+    tokenProgramVersion: TokenProgramVersion.Original,
+    creators: sale.royalties.creators,
+  };
+  return { treeIndex, tierIndex: sale.tierIndex, ticketNumber, section, row, seat, nonce, metadata };
+}
+
+// The per-ticket parts of a leaf's metadata, as the program's TicketLeafMetadata argument
+export function metadataArg(metadata: MetadataArgs) {
+  return {
+    name: metadata.name,
+    uri: metadata.uri,
+    sellerFeeBasisPoints: metadata.sellerFeeBasisPoints,
+    creators: metadata.creators,
+  };
+}
+
+/**
+ * Off-chain copy of an event tree's leaves. Tests record every mint,
+ * transfer and burn here so they can build the root and proof Bubblegum
+ * and account compression check against.
+ */
+export class TicketTree {
+  private leaves: Buffer[];
+
+  constructor(readonly merkleTree: PublicKey, depth: number) {
+    this.leaves = Array.from({ length: 1 << depth }, () => Buffer.alloc(32));
+  }
+
+  // Record `owner` as holder of the leaf, or a burn when owner is null
+  setOwner(leaf: TicketLeaf, owner: PublicKey | null) {
+    this.leaves[leaf.nonce] = owner
+      ? computeCompressedNFTHash(findAssetId(this.merkleTree, leaf.nonce), owner, owner, new BN(leaf.nonce), leaf.metadata)
+      : Buffer.alloc(32);
+  }
+
+  // TicketLeafArgs and proof accounts for an instruction acting on `leaf`
+  proof(leaf: TicketLeaf): { leafArgs: any; proofAccounts: AccountMeta[] } {
+    const { root, proof } = new MerkleTree(this.leaves).getProof(leaf.nonce);
+    return {
+      leafArgs: {
+        treeIndex: leaf.treeIndex,
+        tierIndex: leaf.tierIndex,
+        ticketNumber: leaf.ticketNumber,
+        section: leaf.section,
+        row: leaf.row,
+        seat: leaf.seat,
+        root: [...root],
+        nonce: new BN(leaf.nonce),
+        index: leaf.nonce,
+      },
+      proofAccounts: proof.map((node) => ({ pubkey: new PublicKey(node), isWritable: false, isSigner: false })),
+    };
+  }
+}
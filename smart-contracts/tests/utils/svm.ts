@@ -0,0 +1,56 @@
+import * as path from "path";
+import { PublicKey } from "@solana/web3.js";
+import { LiteSVM } from "litesvm";
+import {
+  BUBBLEGUM_PROGRAM_ID,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  SPL_NOOP_PROGRAM_ID,
+  TOKEN_METADATA_PROGRAM_ID,
+} from "./bubblegum";
+
+// Programs dumped from mainnet by scripts/dump-fixtures.sh
+const FIXTURES: [PublicKey, string][] = [
+  [BUBBLEGUM_PROGRAM_ID, "mpl_bubblegum.so"],
+  [SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, "spl_account_compression.so"],
+  [SPL_NOOP_PROGRAM_ID, "spl_noop.so"],
+  [TOKEN_METADATA_PROGRAM_ID, "mpl_token_metadata.so"],
+];
+
+/**
+ * In-process SVM with the program from `anchor build` and the Metaplex
+ * fixtures loaded. The clock starts at the wall clock and only moves
+ * when a test warps it.
+ */
+export function startSvm(programId: PublicKey): LiteSVM {
+  // Rejection tests resend identical transactions, so keep no history to dedupe against
+  const svm = new LiteSVM().withTransactionHistory(0n);
+  svm.addProgramFromFile(programId, path.join(__dirname, "../../target/deploy/tickettoken.so"));
+  for (const [programId, file] of FIXTURES) {
+    svm.addProgramFromFile(programId, path.join(__dirname, "../fixtures", file));
+  }
+
+  const clock = svm.getClock();
+  clock.unixTimestamp = BigInt(Math.floor(Date.now() / 1000));
+  svm.setClock(clock);
+  return svm;
+}
+
+export function unixNow(svm: LiteSVM): number {
+  return Number(svm.getClock().unixTimestamp);
+}
+
+export function warp(svm: LiteSVM, seconds: number) {
+  const clock = svm.getClock();
+  clock.unixTimestamp += BigInt(seconds);
+  svm.setClock(clock);
+}
+
+export function balanceOf(svm: LiteSVM, pubkey: PublicKey): number {
+  return Number(svm.getBalance(pubkey) ?? 0n);
+}
+
+// A closed account may still be returned, drained of lamports
+export function isClosed(svm: LiteSVM, pubkey: PublicKey): boolean {
+  const account = svm.getAccount(pubkey);
+  return account === null || Number(account.lamports) === 0;
+}